@@ -34,6 +34,36 @@ fn criterion_gakumon(c: &mut Criterion) {
     add_segment_benches(&mut group, &text);
 }
 
+fn criterion_short_docs(c: &mut Criterion) {
+    let mut group = c.benchmark_group("short-docs");
+    group.sample_size(SAMPLE_SIZE);
+    group.warm_up_time(WARM_UP_TIME);
+    group.measurement_time(MEASURE_TIME);
+    group.sampling_mode(SamplingMode::Flat);
+
+    let text = load_text("../data/gakumonno_susume.txt");
+    let docs: Vec<_> = text.lines().collect();
+    let seg = SegmenterBuilder::new()
+        .in_delimiters(["。", "？", "！"])
+        .quotes([('(', ')'), ('「', '」')])
+        .build()
+        .unwrap();
+
+    group.bench_function("segment", |b| {
+        b.iter(|| {
+            let dummy: usize = docs.iter().map(|doc| seg.segment(doc).count()).sum();
+            assert_ne!(dummy, 0);
+        });
+    });
+
+    group.bench_function("segment-batch", |b| {
+        b.iter(|| {
+            let dummy = seg.segment_batch(&docs).ranges().len();
+            assert_ne!(dummy, 0);
+        });
+    });
+}
+
 fn add_segment_benches(group: &mut BenchmarkGroup<WallTime>, text: &str) {
     group.bench_function("only-delimiters", |b| {
         b.iter(|| {
@@ -42,7 +72,7 @@ fn add_segment_benches(group: &mut BenchmarkGroup<WallTime>, text: &str) {
                 .ex_delimiters(["\n", "\r\n", "\r"])
                 .build()
                 .unwrap();
-            let dummy = seg.segment(&text).count();
+            let dummy = seg.segment(text).count();
             assert_ne!(dummy, 0);
         });
     });
@@ -55,7 +85,7 @@ fn add_segment_benches(group: &mut BenchmarkGroup<WallTime>, text: &str) {
                 .quotes([('(', ')'), ('「', '」')])
                 .build()
                 .unwrap();
-            let dummy = seg.segment(&text).count();
+            let dummy = seg.segment(text).count();
             assert_ne!(dummy, 0);
         });
     });
@@ -68,7 +98,7 @@ fn add_segment_benches(group: &mut BenchmarkGroup<WallTime>, text: &str) {
                 .no_break_regex(regex::Regex::new(r"(。{2,})。").unwrap())
                 .build()
                 .unwrap();
-            let dummy = seg.segment(&text).count();
+            let dummy = seg.segment(text).count();
             assert_ne!(dummy, 0);
         });
     });
@@ -83,5 +113,10 @@ fn load_text(filename: &str) -> String {
     text
 }
 
-criterion_group!(benches, criterion_waganeko, criterion_gakumon);
+criterion_group!(
+    benches,
+    criterion_waganeko,
+    criterion_gakumon,
+    criterion_short_docs
+);
 criterion_main!(benches);
@@ -1,5 +1,6 @@
 use std::ops::Range;
 
+#[derive(Default)]
 pub struct Bitset {
     bits: Vec<u64>,
 }
@@ -11,6 +12,12 @@ impl Bitset {
         }
     }
 
+    /// Clears all bits and resizes the bitset, reusing the allocated memory.
+    pub fn reset(&mut self, len: usize) {
+        self.bits.clear();
+        self.bits.resize(Self::words_for(len), 0);
+    }
+
     #[inline(always)]
     pub fn get(&self, i: usize) -> bool {
         let (block, shift) = (i / 64, i % 64);
@@ -50,7 +57,7 @@ impl Bitset {
 
     #[inline(always)]
     const fn words_for(n: usize) -> usize {
-        (n + 63) / 64
+        n.div_ceil(64)
     }
 }
 
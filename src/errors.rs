@@ -1,6 +1,6 @@
 //! Error definitions.
 use std::error::Error;
-use std::{fmt, result};
+use std::{fmt, io, result};
 
 /// A specialized Result type for this library.
 pub type Result<T, E = EasySegmenterError> = result::Result<T, E>;
@@ -13,6 +13,12 @@ pub enum EasySegmenterError {
 
     /// The error variant for [`toml::de::Error`].
     TomlDecode(toml::de::Error),
+
+    /// The error variant for [`toml::ser::Error`].
+    TomlEncode(toml::ser::Error),
+
+    /// The error variant for [`std::io::Error`].
+    Io(io::Error),
 }
 
 impl fmt::Display for EasySegmenterError {
@@ -20,6 +26,8 @@ impl fmt::Display for EasySegmenterError {
         match self {
             Self::Input(e) => e.fmt(f),
             Self::TomlDecode(e) => e.fmt(f),
+            Self::TomlEncode(e) => e.fmt(f),
+            Self::Io(e) => e.fmt(f),
         }
     }
 }
@@ -54,3 +62,15 @@ impl From<toml::de::Error> for EasySegmenterError {
         Self::TomlDecode(error)
     }
 }
+
+impl From<toml::ser::Error> for EasySegmenterError {
+    fn from(error: toml::ser::Error) -> Self {
+        Self::TomlEncode(error)
+    }
+}
+
+impl From<io::Error> for EasySegmenterError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
@@ -2,6 +2,24 @@
 //!
 //! easy-segmenter is a fast and customizable rule-based sentence segmenter library for Rust.
 //!
+//! ## Templates
+//!
+//! Pre-defined segmentation rules are available in [`template`].
+//!
+//! ```rust
+//! let seg = easy_segmenter::template::ja::general().build().unwrap();
+//! let text = "私は「はい。そうです。」と答えた。円周率は３．１４です。";
+//! let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+//! let expected = vec!["私は「はい。そうです。」と答えた。", "円周率は３．１４です。"];
+//! assert_eq!(sentences, expected);
+//! ```
+//!
+//! ## Crate features
+//!
+//! - `rayon`: Enables `Segmenter::segment_par` to segment a long text in parallel.
+//! - `cli`: Builds the `easy-segmenter` command-line tool.
+//! - `nfkc`: Enables `WordNormalization::Nfkc` to match no-break words in NFKC.
+//!
 //! ## How to customize
 //!
 //! `easy_segmenter::Segmenter` does not hardcode any segmentation rules and
@@ -60,6 +78,65 @@
 //! assert_eq!(sentences, expected);
 //! ```
 //!
+//! Runs of adjacent inclusive delimiters can be merged into one without regex.
+//!
+//! ```rust
+//! let seg = easy_segmenter::SegmenterBuilder::new()
+//!     .in_delimiters(["。", "！", "？"])
+//!     .mergeable_delimiters(["。", "！", "？"])
+//!     .build()
+//!     .unwrap();
+//! let text = "えっ！？はぁ。。。。。疲れた。";
+//! let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+//! let expected = vec!["えっ！？", "はぁ。。。。。", "疲れた。"];
+//! assert_eq!(sentences, expected);
+//! ```
+//!
+//! Closing brackets following inclusive delimiters can be attached to the preceding sentences.
+//!
+//! ```rust
+//! let seg = easy_segmenter::SegmenterBuilder::new()
+//!     .in_delimiters(["。"])
+//!     .trailing_attachers(["」", "）"])
+//!     .build()
+//!     .unwrap();
+//! let text = "行った。」と言った。";
+//! let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+//! let expected = vec!["行った。」", "と言った。"];
+//! assert_eq!(sentences, expected);
+//! ```
+//!
+//! Delimiters can be restricted by the adjacent characters, e.g., a period not followed by
+//! a closing bracket, which are checked only at the positions of the delimiters.
+//!
+//! ```rust
+//! use easy_segmenter::segmenter::builder::WordBoundary;
+//!
+//! let seg = easy_segmenter::SegmenterBuilder::new()
+//!     .in_delimiters_with_boundaries(["。"], WordBoundary::Any, WordBoundary::Class("[^）]".to_string()))
+//!     .build()
+//!     .unwrap();
+//! let text = "はい（笑。）いいえ。";
+//! let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+//! let expected = vec!["はい（笑。）いいえ。"];
+//! assert_eq!(sentences, expected);
+//! ```
+//!
+//! Delimiters can be also defined with regex patterns, where the first captured group
+//! (or the whole match if no group) is the delimiter.
+//! They are detected together with the above ones in the leftmost-longest manner.
+//!
+//! ```rust
+//! let seg = easy_segmenter::SegmenterBuilder::new()
+//!     .in_delimiter_regex(regex::Regex::new(r"(\. )[A-Z]").unwrap())
+//!     .build()
+//!     .unwrap();
+//! let text = "It costs 3. 5 dollars. Is it cheap?";
+//! let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+//! let expected = vec!["It costs 3. 5 dollars. ", "Is it cheap?"];
+//! assert_eq!(sentences, expected);
+//! ```
+//!
 //! ### Rules for not segmenting sentences
 //!
 //! easy-segmenter provides three ways to define rules for not segmenting sentences.
@@ -69,7 +146,7 @@
 //!
 //! Quoted sentences will not be segmented.
 //! You can define pairs of characters to specify quotations such as brackets and
-//! parentheses.
+//! parentheses, or pairs of strings such as `("<q>", "</q>")`.
 //!
 //! ```rust
 //! let seg = easy_segmenter::SegmenterBuilder::new()
@@ -99,6 +176,26 @@
 //! assert_eq!(sentences, expected);
 //! ```
 //!
+//! Words can also be restricted by the adjacent characters, which is useful for
+//! abbreviations in English such as `St.` that should not match in `1st.`.
+//!
+//! ```rust
+//! use easy_segmenter::segmenter::builder::WordBoundary;
+//!
+//! let seg = easy_segmenter::SegmenterBuilder::new()
+//!     .in_delimiters([". "])
+//!     .no_break_words_with_boundaries(["St. "], WordBoundary::NonAlphanumeric, WordBoundary::Any)
+//!     .build()
+//!     .unwrap();
+//! let text = "He came 1st. St. Mary won.";
+//! let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+//! let expected = vec!["He came 1st. ", "St. Mary won."];
+//! assert_eq!(sentences, expected);
+//! ```
+//!
+//! Through `no_break_word_patterns`, only the parts enclosed in brackets are protected,
+//! e.g., `e[.]g.` protects the first period but leaves the last one breakable.
+//!
 //! #### 3. Regex
 //!
 //! You can define regex patterns that should not be segmented.
@@ -132,9 +229,50 @@
 //! assert_eq!(sentences, expected);
 //! ```
 //!
+//! Dot sequences can be also handled by `SegmenterBuilder::mergeable_delimiters` without regex.
+//!
 //! Regular expressions are powerful, but complicated ones can slow down segmentation.
 //! *Consider using `no_break_words` first to solve your problem.*
 //!
+//! ### Rules for forcing segmentation
+//!
+//! Force-break rules take precedence over all the rules above,
+//! breaking sentences even inside quotations, e.g., at article headings in legal texts.
+//!
+//! ```rust
+//! let seg = easy_segmenter::SegmenterBuilder::new()
+//!     .in_delimiters(["。"])
+//!     .quotes([('「', '」')])
+//!     .force_break_regex(regex::Regex::new(r"\n(第\d+条)").unwrap())
+//!     .build()
+//!     .unwrap();
+//! let text = "「前文\n第1条　目的。」";
+//! let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+//! let expected = vec!["「前文\n", "第1条　目的。」"];
+//! assert_eq!(sentences, expected);
+//! ```
+//!
+//! ### Trimming sentences
+//!
+//! Leading and trailing whitespace such as indentation can be excluded from resulting ranges,
+//! and sentences that become empty can be dropped.
+//!
+//! ```rust
+//! use easy_segmenter::segmenter::builder::TrimPolicy;
+//!
+//! let seg = easy_segmenter::SegmenterBuilder::new()
+//!     .in_delimiters(["。"])
+//!     .ex_delimiters(["\n"])
+//!     .trim_policy(TrimPolicy::Unicode)
+//!     .drop_empty_sentences(true)
+//!     .build()
+//!     .unwrap();
+//! let text = "　吾輩は猫である。\n　\n　名前はまだ無い。";
+//! let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+//! let expected = vec!["吾輩は猫である。", "名前はまだ無い。"];
+//! assert_eq!(sentences, expected);
+//! ```
+//!
 //! ## Not supported by easy-segmenter
 //!
 //! For simplicity, easy-segmenter does not support any function that requires editing of the original text,
@@ -146,6 +284,10 @@
 //! such as [neologd](https://github.com/neologd/mecab-ipadic-neologd/wiki/Regexp).
 //! Therefore, it should not be included in easy-segmenter.
 //!
+//! Instead, no-break words can be matched against texts in a normalized form
+//! through `SegmenterBuilder::word_normalization` and `SegmenterBuilder::word_ascii_case_insensitive`,
+//! which leave the original text as it is.
+//!
 //! ### Fixing errant line breaks
 //!
 //! Some other tools erase line breaks that are erroneously inserted in a sentence.
@@ -172,13 +314,13 @@
 #![deny(missing_docs)]
 
 pub mod errors;
+pub mod rule;
 pub mod segmenter;
+pub mod template;
 
 mod bitset;
 mod matcher;
+mod normalizer;
 
-// TODO: Make it pub when it is ready.
-#[allow(dead_code)]
-mod rule;
-
-pub use segmenter::{Segmenter, SegmenterBuilder};
+pub use rule::RuleConfig;
+pub use segmenter::{IncrementalSegmenter, Segmenter, SegmenterBuilder, Sentence};
@@ -0,0 +1,318 @@
+//! Command-line interface of easy-segmenter,
+//! which is built only with the `cli` feature, e.g., `cargo install --path . --features cli`.
+//!
+//! ```text
+//! $ echo "はい。そうです。" | easy-segmenter
+//! はい。
+//! そうです。
+//! ```
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Condvar, Mutex};
+
+use clap::{Parser, ValueEnum};
+use serde_derive::Serialize;
+
+use easy_segmenter::segmenter::OwnedSentence;
+use easy_segmenter::{template, Segmenter, SegmenterBuilder};
+
+/// Splits texts into sentences.
+///
+/// The input texts are read from the given files, or from stdin if no file is given.
+#[derive(Parser, Debug)]
+#[command(version)]
+struct Args {
+    /// Input files. `-` denotes stdin.
+    files: Vec<PathBuf>,
+
+    /// TOML file of segmentation rules.
+    #[arg(short, long, conflicts_with = "template")]
+    rules: Option<PathBuf>,
+
+    /// Name of a pre-defined template of segmentation rules.
+    #[arg(short, long, default_value = "ja", value_parser = template::NAMES)]
+    template: String,
+
+    /// Output format.
+    #[arg(short, long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+
+    /// Terminates each record with NUL instead of a newline,
+    /// which is useful for sentences containing newlines.
+    #[arg(short = '0', long)]
+    null: bool,
+
+    /// Number of files processed in parallel. Defaults to the number of CPUs.
+    #[arg(short, long)]
+    jobs: Option<usize>,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum Format {
+    /// One sentence per record.
+    Text,
+    /// JSON object per record with the file name, index, and offsets,
+    /// including the byte range of the quotation broken into the sentence if any.
+    Jsonl,
+    /// Tab-separated columns of the file name, index, byte start, byte end,
+    /// char start, char end, and sentence, where tabs, newlines, and backslashes
+    /// in the sentence are escaped.
+    Tsv,
+}
+
+#[derive(Serialize)]
+struct Record<'a> {
+    file: &'a str,
+    index: usize,
+    start: usize,
+    end: usize,
+    char_start: usize,
+    char_end: usize,
+    text: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parent_quote: Option<(usize, usize)>,
+}
+
+fn main() {
+    let args = Args::parse();
+    let seg = match build_segmenter(&args) {
+        Ok(seg) => seg,
+        Err(e) => exit_with_error(&e),
+    };
+    let result = if args.files.len() <= 1 {
+        let path = args
+            .files
+            .first()
+            .map_or_else(|| Path::new("-"), PathBuf::as_path);
+        let mut out = BufWriter::new(io::stdout().lock());
+        process_file(&seg, &args, path, &mut out).and_then(|()| out.flush())
+    } else {
+        process_files_parallel(&seg, &args)
+    };
+    match result {
+        Ok(()) => {}
+        Err(e) if e.kind() == io::ErrorKind::BrokenPipe => {}
+        Err(e) => exit_with_error(&e),
+    }
+}
+
+fn build_segmenter(args: &Args) -> Result<Segmenter, Box<dyn std::error::Error>> {
+    let builder = match args.rules.as_ref() {
+        Some(path) => SegmenterBuilder::from_toml_file(path)?,
+        None => template::by_name(&args.template).unwrap(),
+    };
+    Ok(builder.build()?)
+}
+
+/// Processes files with worker threads, writing the results in the input order
+/// as soon as the preceding files are written.
+fn process_files_parallel(seg: &Segmenter, args: &Args) -> io::Result<()> {
+    let num_jobs = args
+        .jobs
+        .or_else(|| std::thread::available_parallelism().ok().map(usize::from))
+        .unwrap_or(1)
+        .clamp(1, args.files.len());
+    let next = AtomicUsize::new(0);
+    // Workers run ahead of the output by at most twice the number of jobs,
+    // which bounds the number of results held for reordering.
+    let window = OutputWindow::new(2 * num_jobs);
+    let (tx, rx) = mpsc::channel();
+    std::thread::scope(|s| {
+        for _ in 0..num_jobs {
+            let tx = tx.clone();
+            let (next, window) = (&next, &window);
+            s.spawn(move || loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                if i >= args.files.len() || !window.wait(i) {
+                    break;
+                }
+                let mut buf = vec![];
+                let result = process_file(seg, args, &args.files[i], &mut buf).map(|()| buf);
+                if tx.send((i, result)).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(tx);
+        let result = write_in_order(rx, &window);
+        window.stop();
+        result
+    })
+}
+
+/// Writes the results received from workers in the order of the file indices.
+fn write_in_order(
+    rx: mpsc::Receiver<(usize, io::Result<Vec<u8>>)>,
+    window: &OutputWindow,
+) -> io::Result<()> {
+    let mut out = BufWriter::new(io::stdout().lock());
+    let mut pending = BTreeMap::new();
+    let mut written = 0;
+    for (i, result) in rx {
+        pending.insert(i, result);
+        while let Some(result) = pending.remove(&written) {
+            out.write_all(&result?)?;
+            written += 1;
+            window.advance(written);
+        }
+        out.flush()?;
+    }
+    Ok(())
+}
+
+/// Range of files that workers can process ahead of the output.
+struct OutputWindow {
+    // Number of files written, which is `usize::MAX` if the output is stopped.
+    written: Mutex<usize>,
+    cond: Condvar,
+    len: usize,
+}
+
+impl OutputWindow {
+    const fn new(len: usize) -> Self {
+        Self {
+            written: Mutex::new(0),
+            cond: Condvar::new(),
+            len,
+        }
+    }
+
+    /// Waits until the `i`-th file enters the window,
+    /// returning `false` if the output is stopped.
+    fn wait(&self, i: usize) -> bool {
+        let written = self
+            .cond
+            .wait_while(self.written.lock().unwrap(), |written| {
+                i >= written.saturating_add(self.len)
+            })
+            .unwrap();
+        *written != usize::MAX
+    }
+
+    fn advance(&self, written: usize) {
+        *self.written.lock().unwrap() = written;
+        self.cond.notify_all();
+    }
+
+    /// Stops the workers waiting for the window.
+    fn stop(&self) {
+        self.advance(usize::MAX);
+    }
+}
+
+fn process_file<W>(seg: &Segmenter, args: &Args, path: &Path, out: &mut W) -> io::Result<()>
+where
+    W: Write,
+{
+    let reader: Box<dyn BufRead> = if path == Path::new("-") {
+        Box::new(io::stdin().lock())
+    } else {
+        let file = File::open(path).map_err(|e| with_path(e, path))?;
+        Box::new(BufReader::new(file))
+    };
+    let file = path.to_string_lossy();
+    for sentence in seg.segment_reader(reader) {
+        let sentence = sentence.map_err(|e| with_path(e, path))?;
+        write_record(out, args.format, &file, &sentence)?;
+        out.write_all(if args.null { b"\0" } else { b"\n" })?;
+    }
+    Ok(())
+}
+
+fn write_record<W>(
+    out: &mut W,
+    format: Format,
+    file: &str,
+    sentence: &OwnedSentence,
+) -> io::Result<()>
+where
+    W: Write,
+{
+    match format {
+        Format::Text => out.write_all(sentence.as_str().as_bytes()),
+        Format::Jsonl => {
+            let record = Record {
+                file,
+                index: sentence.index(),
+                start: sentence.byte_range().start,
+                end: sentence.byte_range().end,
+                char_start: sentence.char_range().start,
+                char_end: sentence.char_range().end,
+                text: sentence.as_str(),
+                parent_quote: sentence.parent_quote().map(|r| (r.start, r.end)),
+            };
+            serde_json::to_writer(out, &record).map_err(io::Error::from)
+        }
+        Format::Tsv => write!(
+            out,
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            escape_tsv(file),
+            sentence.index(),
+            sentence.byte_range().start,
+            sentence.byte_range().end,
+            sentence.char_range().start,
+            sentence.char_range().end,
+            escape_tsv(sentence.as_str()),
+        ),
+    }
+}
+
+fn escape_tsv(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\t' => escaped.push_str("\\t"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\\' => escaped.push_str("\\\\"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn with_path(e: io::Error, path: &Path) -> io::Error {
+    io::Error::new(e.kind(), format!("{}: {e}", path.display()))
+}
+
+fn exit_with_error(e: &dyn std::fmt::Display) -> ! {
+    eprintln!("easy-segmenter: {e}");
+    std::process::exit(1);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_tsv() {
+        assert_eq!(escape_tsv("a\tb\r\nc\\"), "a\\tb\\r\\nc\\\\");
+    }
+
+    #[test]
+    fn test_write_record() {
+        let seg = template::ja::general().build().unwrap();
+        let text = "はい。\tそうです。";
+        let sentences: Vec<_> = seg
+            .segment_reader(text.as_bytes())
+            .map(|s| s.unwrap())
+            .collect();
+
+        let mut buf = vec![];
+        write_record(&mut buf, Format::Jsonl, "a.txt", &sentences[1]).unwrap();
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            r#"{"file":"a.txt","index":1,"start":9,"end":25,"char_start":3,"char_end":9,"text":"\tそうです。"}"#
+        );
+
+        let mut buf = vec![];
+        write_record(&mut buf, Format::Tsv, "a.txt", &sentences[1]).unwrap();
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            "a.txt\t1\t9\t25\t3\t9\t\\tそうです。"
+        );
+    }
+}
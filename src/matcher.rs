@@ -1,8 +1,13 @@
+use std::ops::Range;
+
 use aho_corasick::{AhoCorasick, AhoCorasickBuilder, MatchKind};
+use regex::Regex;
 
 use crate::errors::{EasySegmenterError, Result};
+use crate::normalizer;
+use crate::segmenter::builder::{WordBoundary, WordMatcherKind, WordNormalization};
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct DelimiterMatch {
     pub start: usize,
     pub end: usize,
@@ -12,10 +17,26 @@ pub struct DelimiterMatch {
 pub struct DelimiterMatcher {
     pma: AhoCorasick,
     num_in_delimiters: usize,
+    bounded: Option<BoundedDelimiters>,
+    // Regex patterns with the flags of whether they are inclusive.
+    regexes: Vec<(Regex, bool)>,
+    // Inclusive delimiters whose adjacent runs are merged into one.
+    mergeable_delimiters: Vec<String>,
+    // Sorted in descending order of length to attach the longest one.
+    trailing_attachers: Vec<String>,
+    max_len: usize,
 }
 
 impl DelimiterMatcher {
-    pub fn new<P>(in_delimiters: &[P], ex_delimiters: &[P]) -> Self
+    pub fn new<P>(
+        in_delimiters: &[P],
+        ex_delimiters: &[P],
+        in_bounded_delimiters: &[(String, WordBoundary, WordBoundary)],
+        ex_bounded_delimiters: &[(String, WordBoundary, WordBoundary)],
+        regexes: Vec<(Regex, bool)>,
+        mergeable_delimiters: &[String],
+        trailing_attachers: &[String],
+    ) -> Result<Self>
     where
         P: AsRef<str>,
     {
@@ -32,67 +53,352 @@ impl DelimiterMatcher {
             .auto_configure(&patterns)
             .match_kind(MatchKind::LeftmostLongest)
             .build(&patterns);
-        Self {
+        let bounded = if in_bounded_delimiters.is_empty() && ex_bounded_delimiters.is_empty() {
+            None
+        } else {
+            Some(BoundedDelimiters::new(
+                in_bounded_delimiters,
+                ex_bounded_delimiters,
+            )?)
+        };
+        let bounded_len = bounded.as_ref().map_or(0, |b| b.max_len);
+        let mut trailing_attachers = trailing_attachers.to_vec();
+        trailing_attachers.sort_by_key(|a| std::cmp::Reverse(a.len()));
+        Ok(Self {
             pma,
             num_in_delimiters: in_delimiters.len(),
+            bounded,
+            regexes,
+            mergeable_delimiters: mergeable_delimiters.to_vec(),
+            max_len: max_len(&patterns)
+                .max(bounded_len)
+                .max(max_len(&trailing_attachers)),
+            trailing_attachers,
+        })
+    }
+
+    /// Returns the maximum length of literal delimiters and trailing attachers.
+    pub const fn max_len(&self) -> usize {
+        self.max_len
+    }
+
+    /// Returns the regex patterns for delimiters.
+    pub fn regexes(&self) -> impl Iterator<Item = &Regex> {
+        self.regexes.iter().map(|(re, _)| re)
+    }
+
+    /// Checks if a delimiter can be merged with the adjacent ones.
+    pub fn is_mergeable(&self, text: &str, m: &DelimiterMatch) -> bool {
+        m.is_in_delimiter
+            && self
+                .mergeable_delimiters
+                .iter()
+                .any(|d| d == &text[m.start..m.end])
+    }
+
+    /// Returns the end of the trailing attachers that immediately follow `pos`
+    /// and end by `limit`, which is `pos` if no attacher follows.
+    pub fn attach_trailing(&self, text: &str, mut pos: usize, limit: usize) -> usize {
+        while let Some(a) = self
+            .trailing_attachers
+            .iter()
+            .find(|a| text[pos..limit].starts_with(a.as_str()))
+        {
+            pos += a.len();
         }
+        pos
+    }
+
+    /// Checks if a literal delimiter can be matched across `pos`.
+    #[cfg(feature = "rayon")]
+    pub fn occurs_across(&self, text: &str, pos: usize) -> bool {
+        occurs_across_leftmost(&self.pma, self.max_len, text, pos)
+            || self
+                .bounded
+                .as_ref()
+                .is_some_and(|b| b.occurs_across(text, pos))
     }
 
-    pub fn iter<'a>(&'a self, text: &'a str) -> impl Iterator<Item = DelimiterMatch> + 'a {
+    /// Finds delimiters starting in `text[range]` without overlaps,
+    /// where literal delimiters must end within the range while regex patterns are
+    /// matched against the whole `text` and can end after the range.
+    /// The characters adjacent to the range are used to check the boundaries of delimiters.
+    ///
+    /// Among all the delimiters, the leftmost-longest one is selected, preferring
+    /// literal ones, bounded ones, and then regex patterns in the order of registration.
+    pub fn iter<'a>(
+        &'a self,
+        text: &'a str,
+        range: Range<usize>,
+    ) -> impl Iterator<Item = DelimiterMatch> + 'a {
+        let end = range.end;
+        let mut pos = range.start;
+        // The next match of each matcher from `pos`, which is searched again
+        // when it starts before `pos`. The first two are for literal and bounded delimiters.
+        let mut candidates: Vec<Candidate> = vec![Candidate::Unknown; self.regexes.len() + 2];
+        let mut terminated = false;
+        std::iter::from_fn(move || {
+            let mut best: Option<DelimiterMatch> = None;
+            for (i, candidate) in candidates.iter_mut().enumerate() {
+                if candidate.is_stale(pos) {
+                    *candidate = match i {
+                        0 => self.find_literal(text, pos..end),
+                        1 => self
+                            .bounded
+                            .as_ref()
+                            .map_or(Candidate::NotFound, |b| b.find(text, pos..end)),
+                        _ => self.find_regex(i - 2, text, pos..end),
+                    };
+                }
+                if let Candidate::Found(_, m) = candidate {
+                    if best.is_none_or(|b| (m.start, b.end) < (b.start, m.end)) {
+                        best = Some(*m);
+                    }
+                }
+            }
+            match best {
+                Some(m) if m.start < end => {
+                    pos = m.end;
+                    Some(m)
+                }
+                // Always returns an imaginary terminator to address the case that
+                // the last character does not have any delimiter.
+                _ if !terminated => {
+                    terminated = true;
+                    Some(DelimiterMatch {
+                        start: end,
+                        end,
+                        is_in_delimiter: false,
+                    })
+                }
+                _ => None,
+            }
+        })
+    }
+
+    fn find_literal(&self, text: &str, range: Range<usize>) -> Candidate {
+        let from = range.start;
         self.pma
-            .find_iter(text)
-            .map(move |m| DelimiterMatch {
-                start: m.start(),
-                end: m.end(),
-                is_in_delimiter: m.pattern() < self.num_in_delimiters,
+            .find(&text[range])
+            .map_or(Candidate::NotFound, |m| {
+                let m = DelimiterMatch {
+                    start: from + m.start(),
+                    end: from + m.end(),
+                    is_in_delimiter: m.pattern() < self.num_in_delimiters,
+                };
+                Candidate::Found(m.start, m)
             })
-            // Always returns an imaginary terminator to address the case that
-            // the last character does not have any delimiter.
-            .chain([DelimiterMatch {
-                start: text.len(),
-                end: text.len(),
-                is_in_delimiter: false,
-            }])
+    }
+
+    /// Finds the first match of a regex pattern starting in `range`
+    /// whose delimiter, i.e., the first group or the whole match, is not empty.
+    fn find_regex(&self, idx: usize, text: &str, range: Range<usize>) -> Candidate {
+        let (re, is_in_delimiter) = &self.regexes[idx];
+        let mut pos = range.start;
+        while pos <= text.len() {
+            let Some(cap) = re.captures_at(text, pos) else {
+                break;
+            };
+            let whole = cap.get(0).unwrap();
+            if whole.start() >= range.end {
+                break;
+            }
+            let delimiter = if cap.len() > 1 {
+                cap.get(1)
+            } else {
+                Some(whole)
+            };
+            if let Some(d) = delimiter.filter(|d| !d.is_empty()) {
+                let m = DelimiterMatch {
+                    start: d.start(),
+                    end: d.end(),
+                    is_in_delimiter: *is_in_delimiter,
+                };
+                return Candidate::Found(whole.start(), m);
+            }
+            pos = if whole.is_empty() {
+                whole.end() + text[whole.end()..].chars().next().map_or(1, char::len_utf8)
+            } else {
+                whole.end()
+            };
+        }
+        Candidate::NotFound
+    }
+}
+
+/// Literal delimiters with conditions on the adjacent characters.
+struct BoundedDelimiters {
+    // Overlapping matches are needed to try shorter delimiters whose conditions are satisfied.
+    pma: AhoCorasick,
+    num_in_delimiters: usize,
+    boundaries: Boundaries,
+    max_len: usize,
+}
+
+impl BoundedDelimiters {
+    fn new(
+        in_delimiters: &[(String, WordBoundary, WordBoundary)],
+        ex_delimiters: &[(String, WordBoundary, WordBoundary)],
+    ) -> Result<Self> {
+        let delimiters: Vec<_> = in_delimiters.iter().chain(ex_delimiters).collect();
+        let patterns: Vec<_> = delimiters.iter().map(|(d, _, _)| d).collect();
+        let pma = AhoCorasickBuilder::new()
+            .auto_configure(&patterns)
+            .build(&patterns);
+        let boundaries = Boundaries::new(delimiters.iter().map(|(_, b, a)| (b, a)))?;
+        Ok(Self {
+            pma,
+            num_in_delimiters: in_delimiters.len(),
+            boundaries,
+            max_len: max_len(&patterns),
+        })
+    }
+
+    /// Checks if a delimiter can be matched across `pos` regardless of the conditions.
+    #[cfg(feature = "rayon")]
+    fn occurs_across(&self, text: &str, pos: usize) -> bool {
+        let start = (pos + 1).saturating_sub(self.max_len);
+        let end = (pos + self.max_len).saturating_sub(1).min(text.len());
+        self.pma
+            .find_overlapping_iter(&text.as_bytes()[start..end.max(start)])
+            .any(|m| start + m.start() < pos && pos < start + m.end())
+    }
+
+    /// Finds the leftmost-longest delimiter in `text[range]` satisfying the conditions.
+    fn find(&self, text: &str, range: Range<usize>) -> Candidate {
+        let from = range.start;
+        let mut best: Option<DelimiterMatch> = None;
+        // Overlapping matches are reported in the order of their end positions.
+        for m in self.pma.find_overlapping_iter(&text[range]) {
+            let (start, end) = (from + m.start(), from + m.end());
+            if best.is_some_and(|b| end > b.start + self.max_len) {
+                break;
+            }
+            if !self.boundaries.accepts(text, m.pattern(), start..end) {
+                continue;
+            }
+            if best.is_none_or(|b| (start, b.end) < (b.start, end)) {
+                best = Some(DelimiterMatch {
+                    start,
+                    end,
+                    is_in_delimiter: m.pattern() < self.num_in_delimiters,
+                });
+            }
+        }
+        best.map_or(Candidate::NotFound, |m| Candidate::Found(m.start, m))
+    }
+}
+
+/// Next match of a delimiter matcher.
+#[derive(Clone, Copy)]
+enum Candidate {
+    Unknown,
+    /// Match with the start position of the whole regex match.
+    Found(usize, DelimiterMatch),
+    NotFound,
+}
+
+impl Candidate {
+    /// Checks if the candidate needs to be searched again from `pos`.
+    const fn is_stale(&self, pos: usize) -> bool {
+        match self {
+            Self::Unknown => true,
+            Self::Found(start, _) => *start < pos,
+            Self::NotFound => false,
+        }
     }
 }
 
+/// Kind of a quotation mark.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QuoteKind {
+    Open,
+    Close,
+    /// Mark that can either open or close a quotation.
+    Symmetric,
+    /// Delimiter that discards all open quotations, whose pair id is meaningless.
+    Reset,
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub struct QuoteMatch {
     pub start: usize,
     pub end: usize,
     pub id: usize,
-    pub is_open: bool,
+    pub kind: QuoteKind,
 }
 
 pub struct QuoteMatcher {
     pma: AhoCorasick,
+    // Pair id and kind of each pattern.
+    marks: Vec<(usize, QuoteKind)>,
+    max_len: usize,
 }
 
 impl QuoteMatcher {
-    pub fn new(quotes: &[(char, char)]) -> Result<Self> {
+    pub fn new<P, R>(quotes: &[(P, P)], resets: &[R]) -> Result<Self>
+    where
+        P: AsRef<str>,
+        R: AsRef<str>,
+    {
         let mut patterns = vec![];
-        for &(p, q) in quotes {
-            patterns.push(p.to_string());
-            patterns.push(q.to_string());
+        let mut marks = vec![];
+        for (id, (p, q)) in quotes.iter().enumerate() {
+            let (p, q) = (p.as_ref(), q.as_ref());
+            if p.is_empty() || q.is_empty() {
+                return Err(EasySegmenterError::input("Entries must not be empty."));
+            }
+            patterns.push(p);
+            if p == q {
+                marks.push((id, QuoteKind::Symmetric));
+            } else {
+                marks.push((id, QuoteKind::Open));
+                patterns.push(q);
+                marks.push((id, QuoteKind::Close));
+            }
+        }
+        for r in resets {
+            let r = r.as_ref();
+            if r.is_empty() {
+                return Err(EasySegmenterError::input("Entries must not be empty."));
+            }
+            patterns.push(r);
+            marks.push((usize::MAX, QuoteKind::Reset));
         }
         if !is_unique(&patterns) {
             return Err(EasySegmenterError::input("Entries must be unique."));
         }
+        // Multi-character marks such as `<<` take precedence over their prefixes such as `<`.
         let pma = AhoCorasickBuilder::new()
             .auto_configure(&patterns)
+            .match_kind(MatchKind::LeftmostLongest)
             .build(&patterns);
-        Ok(Self { pma })
+        Ok(Self {
+            pma,
+            marks,
+            max_len: max_len(&patterns),
+        })
+    }
+
+    pub const fn max_len(&self) -> usize {
+        self.max_len
     }
 
-    pub fn iter<'a>(&'a self, text: &'a str) -> impl Iterator<Item = QuoteMatch> + 'a {
-        self.pma.find_iter(text).map(move |m| {
-            let id = m.pattern() / 2;
-            let is_open = m.pattern() % 2 == 0;
+    /// Checks if a quotation mark can be matched across `pos`.
+    #[cfg(feature = "rayon")]
+    pub fn occurs_across(&self, text: &str, pos: usize) -> bool {
+        occurs_across_leftmost(&self.pma, self.max_len, text, pos)
+    }
+
+    /// Finds quotation marks in `text[from..]`.
+    pub fn iter<'a>(&'a self, text: &'a str, from: usize) -> impl Iterator<Item = QuoteMatch> + 'a {
+        self.pma.find_iter(&text[from..]).map(move |m| {
+            let (id, kind) = self.marks[m.pattern()];
             QuoteMatch {
-                start: m.start(),
-                end: m.end(),
+                start: from + m.start(),
+                end: from + m.end(),
                 id,
-                is_open,
+                kind,
             }
         })
     }
@@ -102,30 +408,321 @@ impl QuoteMatcher {
 pub struct WordMatch {
     pub start: usize,
     pub end: usize,
+    /// Protected parts of the word, which is empty if the whole word is protected.
+    pub parts: Vec<Range<usize>>,
 }
 
 pub struct WordMatcher {
     pma: AhoCorasick,
+    normalization: WordNormalization,
+    boundaries: Boundaries,
+    // Protected parts in the normalized patterns of words that are partially protected,
+    // sorted by the pattern ids.
+    parts: Vec<(usize, Range<usize>)>,
+    max_len: usize,
 }
 
 impl WordMatcher {
-    pub fn new<P>(words: &[P]) -> Self
+    /// Creates a matcher of literal `words` and `word_patterns` in the syntax of [`parse_word`].
+    pub fn new(
+        words: &[(String, WordBoundary, WordBoundary)],
+        word_patterns: &[String],
+        ascii_case_insensitive: bool,
+        normalization: WordNormalization,
+        kind: WordMatcherKind,
+    ) -> Result<Self> {
+        let mut patterns = Vec::with_capacity(words.len() + word_patterns.len());
+        let mut parts = vec![];
+        for (w, _, _) in words {
+            patterns.push(normalizer::normalize(w, normalization).as_str().to_string());
+        }
+        for (id, w) in word_patterns.iter().enumerate() {
+            let id = words.len() + id;
+            let segments = parse_word(w).map_err(|e| {
+                EasySegmenterError::input(format!("The word pattern {w:?} is invalid: {e}"))
+            })?;
+            let is_partial = segments.iter().any(|(_, is_protected)| !is_protected);
+            let mut pattern = String::new();
+            for (segment, is_protected) in segments {
+                let start = pattern.len();
+                pattern.push_str(normalizer::normalize(&segment, normalization).as_str());
+                if is_partial && is_protected {
+                    parts.push((id, start..pattern.len()));
+                }
+            }
+            patterns.push(pattern);
+        }
+        let mut builder = AhoCorasickBuilder::new();
+        match kind {
+            WordMatcherKind::Auto => builder.auto_configure(&patterns),
+            WordMatcherKind::Dfa => builder.dfa(true),
+            // Dense transitions are disabled to minimize memory usage.
+            WordMatcherKind::Nfa => builder.dfa(false).dense_depth(0),
+        };
+        let pma = builder
+            .ascii_case_insensitive(ascii_case_insensitive)
+            .build(&patterns);
+        let boundaries = Boundaries::new(
+            words.iter().map(|(_, b, a)| (b, a)).chain(
+                word_patterns
+                    .iter()
+                    .map(|_| (&WordBoundary::Any, &WordBoundary::Any)),
+            ),
+        )?;
+        Ok(Self {
+            pma,
+            normalization,
+            boundaries,
+            parts,
+            max_len: normalizer::max_original_len(max_len(&patterns), normalization),
+        })
+    }
+
+    /// Returns the maximum length of words in the original text.
+    pub const fn max_len(&self) -> usize {
+        self.max_len
+    }
+
+    /// Checks if a word can be matched across `pos`.
+    #[cfg(feature = "rayon")]
+    pub fn occurs_across(&self, text: &str, pos: usize) -> bool {
+        let start = normalizer::unit_start(
+            text,
+            (pos + 1).saturating_sub(self.max_len),
+            self.normalization,
+        );
+        let end =
+            normalizer::char_end(text, (pos + self.max_len).saturating_sub(1).min(text.len()));
+        if start >= pos || end <= pos {
+            return false;
+        }
+        self.iter(text, start..end)
+            .any(|m| m.start < pos && pos < m.end)
+    }
+
+    /// Checks if a normalization unit can start at `pos`, from which words are found
+    /// in the same manner as the preceding text.
+    pub fn is_unit_start(&self, text: &str, pos: usize) -> bool {
+        normalizer::unit_start(text, pos, self.normalization) == pos
+    }
+
+    /// Finds words in `text[range]`, including overlapping ones.
+    /// The characters adjacent to the range are used to check the boundaries of words.
+    pub fn iter<'a>(
+        &'a self,
+        text: &'a str,
+        range: Range<usize>,
+    ) -> impl Iterator<Item = WordMatch> + 'a {
+        let from = range.start;
+        let matches: Box<dyn Iterator<Item = (usize, WordMatch)>> =
+            if self.normalization == WordNormalization::None {
+                Box::new(self.pma.find_overlapping_iter(&text[range]).map(move |m| {
+                    let start = from + m.start();
+                    let word = WordMatch {
+                        start,
+                        end: from + m.end(),
+                        parts: self
+                            .parts_of(m.pattern())
+                            .map(|r| start + r.start..start + r.end)
+                            .collect(),
+                    };
+                    (m.pattern(), word)
+                }))
+            } else {
+                Box::new(self.find_normalized(&text[..range.end], from).into_iter())
+            };
+        matches
+            .filter(move |(pattern, m)| self.boundaries.accepts(text, *pattern, m.start..m.end))
+            .map(|(_, m)| m)
+    }
+
+    /// Finds words in `text[from..]` through the normalized text.
+    fn find_normalized(&self, text: &str, from: usize) -> Vec<(usize, WordMatch)> {
+        let normalized = normalizer::normalize(&text[from..], self.normalization);
+        self.pma
+            .find_overlapping_iter(normalized.as_str())
+            .filter_map(|m| {
+                let range = normalized.original_range(m.start()..m.end())?;
+                let mut parts = vec![];
+                for r in self.parts_of(m.pattern()) {
+                    // The whole word is protected if a part does not correspond
+                    // to the original characters.
+                    let Some(r) = normalized.original_range(m.start() + r.start..m.start() + r.end)
+                    else {
+                        parts.clear();
+                        break;
+                    };
+                    parts.push(from + r.start..from + r.end);
+                }
+                let word = WordMatch {
+                    start: from + range.start,
+                    end: from + range.end,
+                    parts,
+                };
+                Some((m.pattern(), word))
+            })
+            .collect()
+    }
+
+    /// Returns the protected parts of a partially protected word in the normalized pattern.
+    fn parts_of(&self, pattern: usize) -> impl Iterator<Item = &Range<usize>> {
+        let start = self.parts.partition_point(|(id, _)| *id < pattern);
+        self.parts[start..]
+            .iter()
+            .take_while(move |(id, _)| *id == pattern)
+            .map(|(_, r)| r)
+    }
+}
+
+/// Conditions on the characters adjacent to patterns.
+struct Boundaries {
+    // Indices of the classes of the preceding and following characters of each pattern,
+    // which is empty if no pattern has such conditions.
+    indices: Vec<(Option<usize>, Option<usize>)>,
+    classes: Vec<Regex>,
+}
+
+impl Boundaries {
+    fn new<'a, I>(conditions: I) -> Result<Self>
     where
-        P: AsRef<str>,
+        I: IntoIterator<Item = (&'a WordBoundary, &'a WordBoundary)>,
     {
-        let patterns: Vec<_> = words.iter().map(|p| p.as_ref()).collect();
-        let pma = AhoCorasick::new_auto_configured(&patterns);
-        Self { pma }
+        // Each distinct condition is compiled only once since dictionaries can be large.
+        let mut compiled: Vec<(&WordBoundary, Option<usize>)> = vec![];
+        let mut classes = vec![];
+        let mut class_index = |boundary: &'a WordBoundary| -> Result<Option<usize>> {
+            if let Some((_, idx)) = compiled.iter().find(|(b, _)| *b == boundary) {
+                return Ok(*idx);
+            }
+            let idx = boundary.to_regex()?.map(|re| {
+                classes.push(re);
+                classes.len() - 1
+            });
+            compiled.push((boundary, idx));
+            Ok(idx)
+        };
+        let mut indices = vec![];
+        for (before, after) in conditions {
+            indices.push((class_index(before)?, class_index(after)?));
+        }
+        if indices.iter().all(|&(b, a)| b.is_none() && a.is_none()) {
+            indices.clear();
+        }
+        Ok(Self { indices, classes })
     }
 
-    pub fn iter<'a>(&'a self, text: &'a str) -> impl Iterator<Item = WordMatch> + 'a {
+    /// Checks if the characters adjacent to `text[range]` satisfy the conditions of a pattern.
+    fn accepts(&self, text: &str, pattern: usize, range: Range<usize>) -> bool {
+        let Some(&(before, after)) = self.indices.get(pattern) else {
+            return true;
+        };
+        let accepts = |class: Option<usize>, c: Option<char>| match (class, c) {
+            (Some(idx), Some(c)) => self.classes[idx].is_match(c.encode_utf8(&mut [0; 4])),
+            _ => true,
+        };
+        accepts(before, text[..range.start].chars().next_back())
+            && accepts(after, text[range.end..].chars().next())
+    }
+}
+
+pub struct ForceBreakMatcher {
+    pma: AhoCorasick,
+    max_len: usize,
+}
+
+impl ForceBreakMatcher {
+    pub fn new(words: &[String]) -> Self {
+        let pma = AhoCorasickBuilder::new().auto_configure(words).build(words);
+        Self {
+            pma,
+            max_len: max_len(words),
+        }
+    }
+
+    pub const fn max_len(&self) -> usize {
+        self.max_len
+    }
+
+    /// Checks if a word can be matched across `pos`.
+    #[cfg(feature = "rayon")]
+    pub fn occurs_across(&self, text: &str, pos: usize) -> bool {
+        let start = (pos + 1).saturating_sub(self.max_len);
+        let end = (pos + self.max_len).saturating_sub(1).min(text.len());
+        self.iter(text, start..end)
+            .any(|m| m.start < pos && pos < m.end)
+    }
+
+    /// Finds words in `text[range]`, including overlapping ones,
+    /// where `range` need not be aligned to character boundaries.
+    pub fn iter<'a>(
+        &'a self,
+        text: &'a str,
+        range: Range<usize>,
+    ) -> impl Iterator<Item = Range<usize>> + 'a {
+        let from = range.start;
         self.pma
-            .find_overlapping_iter(text)
-            .map(move |m| WordMatch {
-                start: m.start(),
-                end: m.end(),
-            })
+            .find_overlapping_iter(&text.as_bytes()[range])
+            .map(move |m| from + m.start()..from + m.end())
+    }
+}
+
+/// Parses a no-break word pattern into segments with the flags of whether they are protected.
+///
+/// Parts enclosed in brackets such as `e[.]g.` are protected and the others are only matched,
+/// where the whole word is protected if it has no brackets.
+/// `\[`, `\]`, and `\\` represent the literal characters.
+pub fn parse_word(word: &str) -> Result<Vec<(String, bool)>, &'static str> {
+    let mut segments = vec![(String::new(), false)];
+    let mut in_brackets = false;
+    let mut chars = word.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if matches!(chars.peek(), Some('[' | ']' | '\\')) => {
+                let escaped = chars.next().unwrap();
+                segments.last_mut().unwrap().0.push(escaped);
+            }
+            '[' | ']' => {
+                if (c == '[') == in_brackets {
+                    return Err("Brackets must be balanced and not nested.");
+                }
+                if in_brackets && segments.last().unwrap().0.is_empty() {
+                    return Err("Brackets must not be empty.");
+                }
+                in_brackets = !in_brackets;
+                segments.push((String::new(), in_brackets));
+            }
+            _ => segments.last_mut().unwrap().0.push(c),
+        }
+    }
+    if in_brackets {
+        return Err("Brackets must be balanced and not nested.");
     }
+    if segments.len() == 1 {
+        segments[0].1 = true;
+    }
+    segments.retain(|(segment, _)| !segment.is_empty());
+    Ok(segments)
+}
+
+fn max_len<S>(patterns: &[S]) -> usize
+where
+    S: AsRef<str>,
+{
+    patterns.iter().map(|p| p.as_ref().len()).max().unwrap_or(0)
+}
+
+/// Checks if any leftmost-longest match can occur across `pos`,
+/// i.e., start before and end after `pos`.
+#[cfg(feature = "rayon")]
+fn occurs_across_leftmost(pma: &AhoCorasick, max_len: usize, text: &str, pos: usize) -> bool {
+    // Since the leftmost-longest match is the longest one starting at the leftmost position,
+    // it suffices to find a match at each possible starting position.
+    let text = text.as_bytes();
+    ((pos + 1).saturating_sub(max_len)..pos).any(|start| {
+        let end = (start + max_len).min(text.len());
+        pma.find(&text[start..end])
+            .is_some_and(|m| m.start() == 0 && start + m.end() > pos)
+    })
 }
 
 fn is_unique<S>(x: &[S]) -> bool
@@ -142,43 +739,420 @@ where
 mod tests {
     use super::*;
 
+    fn words(words: &[&str]) -> Vec<(String, WordBoundary, WordBoundary)> {
+        words
+            .iter()
+            .map(|w| (w.to_string(), WordBoundary::Any, WordBoundary::Any))
+            .collect()
+    }
+
     #[test]
     fn test_quote_1() {
-        let quotes = vec![('「', '」'), ('（', '）')];
-        let matcher = QuoteMatcher::new(&quotes).unwrap();
-        let matches: Vec<_> = matcher.iter("「」（）").collect();
+        let quotes = vec![("「", "」"), ("（", "）")];
+        let matcher = QuoteMatcher::new(&quotes, &[] as &[&str]).unwrap();
+        let matches: Vec<_> = matcher.iter("「」（）", 0).collect();
         let expected = vec![
             QuoteMatch {
                 start: 0,
                 end: 3,
                 id: 0,
-                is_open: true,
+                kind: QuoteKind::Open,
             },
             QuoteMatch {
                 start: 3,
                 end: 6,
                 id: 0,
-                is_open: false,
+                kind: QuoteKind::Close,
             },
             QuoteMatch {
                 start: 6,
                 end: 9,
                 id: 1,
-                is_open: true,
+                kind: QuoteKind::Open,
             },
             QuoteMatch {
                 start: 9,
                 end: 12,
                 id: 1,
-                is_open: false,
+                kind: QuoteKind::Close,
+            },
+        ];
+        assert_eq!(matches, expected);
+    }
+
+    #[test]
+    fn test_quote_symmetric() {
+        let quotes = vec![("\"", "\""), ("「", "」")];
+        let matcher = QuoteMatcher::new(&quotes, &[] as &[&str]).unwrap();
+        let matches: Vec<_> = matcher.iter("\"「", 0).collect();
+        let expected = vec![
+            QuoteMatch {
+                start: 0,
+                end: 1,
+                id: 0,
+                kind: QuoteKind::Symmetric,
+            },
+            QuoteMatch {
+                start: 1,
+                end: 4,
+                id: 1,
+                kind: QuoteKind::Open,
+            },
+        ];
+        assert_eq!(matches, expected);
+    }
+
+    #[test]
+    fn test_quote_multi_char() {
+        let quotes = vec![("<", ">"), ("<<", ">>"), ("```", "```")];
+        let matcher = QuoteMatcher::new(&quotes, &[] as &[&str]).unwrap();
+        let matches: Vec<_> = matcher.iter("<<a>>```", 0).collect();
+        let expected = vec![
+            QuoteMatch {
+                start: 0,
+                end: 2,
+                id: 1,
+                kind: QuoteKind::Open,
+            },
+            QuoteMatch {
+                start: 3,
+                end: 5,
+                id: 1,
+                kind: QuoteKind::Close,
+            },
+            QuoteMatch {
+                start: 5,
+                end: 8,
+                id: 2,
+                kind: QuoteKind::Symmetric,
             },
         ];
         assert_eq!(matches, expected);
     }
 
+    #[test]
+    fn test_quote_reset() {
+        let matcher = QuoteMatcher::new(&[("「", "」")], &["\n\n"]).unwrap();
+        let matches: Vec<_> = matcher.iter("「\n\n", 0).map(|m| m.kind).collect();
+        assert_eq!(matches, vec![QuoteKind::Open, QuoteKind::Reset]);
+        assert!(QuoteMatcher::new(&[("「", "」")], &["」"]).is_err());
+    }
+
+    #[test]
+    fn test_quote_empty() {
+        assert!(QuoteMatcher::new(&[("<q>", "")], &[] as &[&str]).is_err());
+    }
+
     #[test]
     fn test_quote_2() {
-        let quotes = vec![('「', '」'), ('（', '」')];
-        assert!(QuoteMatcher::new(&quotes).is_err());
+        let quotes = vec![("「", "」"), ("（", "」")];
+        assert!(QuoteMatcher::new(&quotes, &[] as &[&str]).is_err());
+    }
+
+    #[test]
+    fn test_word_matcher_kind() {
+        let words: Vec<_> = (0..10000)
+            .map(|i| (format!("w{i}."), WordBoundary::Any, WordBoundary::Any))
+            .collect();
+        let text = "w1.w12.w123.w99999.";
+        let mut results = vec![];
+        for kind in [
+            WordMatcherKind::Auto,
+            WordMatcherKind::Dfa,
+            WordMatcherKind::Nfa,
+        ] {
+            let matcher =
+                WordMatcher::new(&words, &[], false, WordNormalization::None, kind).unwrap();
+            let mut matches: Vec<_> = matcher
+                .iter(text, 0..text.len())
+                .map(|m| (m.start, m.end))
+                .collect();
+            matches.sort_unstable();
+            results.push(matches);
+        }
+        assert_eq!(results[0], vec![(0, 3), (3, 7), (7, 12)]);
+        assert_eq!(results[0], results[1]);
+        assert_eq!(results[0], results[2]);
+    }
+
+    #[test]
+    fn test_word_normalization() {
+        let matcher = WordMatcher::new(
+            &words(&["Mr.", "ガ"]),
+            &[],
+            true,
+            WordNormalization::FoldWidth,
+            WordMatcherKind::Auto,
+        )
+        .unwrap();
+        let text = "MR.Ｍｒ．ｶﾞｶ";
+        let matches: Vec<_> = matcher.iter(text, 0..text.len()).collect();
+        let expected = vec![
+            WordMatch {
+                start: 0,
+                end: 3,
+                parts: vec![],
+            },
+            WordMatch {
+                start: 3,
+                end: 12,
+                parts: vec![],
+            },
+            WordMatch {
+                start: 12,
+                end: 18,
+                parts: vec![],
+            },
+        ];
+        assert_eq!(matches, expected);
+        assert_eq!(matcher.max_len(), 9);
+
+        let matcher = WordMatcher::new(
+            &words(&["Mr."]),
+            &[],
+            false,
+            WordNormalization::None,
+            WordMatcherKind::Auto,
+        )
+        .unwrap();
+        assert_eq!(matcher.iter("MR.Mr.", 0..6).count(), 1);
+    }
+
+    #[test]
+    fn test_parse_word() {
+        assert_eq!(parse_word("e.g."), Ok(vec![("e.g.".to_string(), true)]));
+        let expected = vec![
+            ("e".to_string(), false),
+            (".".to_string(), true),
+            ("g.".to_string(), false),
+        ];
+        assert_eq!(parse_word("e[.]g."), Ok(expected));
+        let expected = vec![("[1]".to_string(), false), ("\\".to_string(), true)];
+        assert_eq!(parse_word(r"\[1\][\\]"), Ok(expected));
+        assert_eq!(parse_word(r"\d"), Ok(vec![(r"\d".to_string(), true)]));
+        assert!(parse_word("e[.g.").is_err());
+        assert!(parse_word("e].g.").is_err());
+        assert!(parse_word("e[[.]]g.").is_err());
+        assert!(parse_word("e[]g.").is_err());
+    }
+
+    #[test]
+    #[allow(clippy::single_range_in_vec_init)]
+    fn test_word_parts() {
+        let matcher = WordMatcher::new(
+            &[],
+            &["e[.]g.".to_string(), "U[.]S[.]".to_string()],
+            false,
+            WordNormalization::None,
+            WordMatcherKind::Auto,
+        )
+        .unwrap();
+        let text = "e.g.U.S.";
+        let matches: Vec<_> = matcher.iter(text, 0..text.len()).collect();
+        let expected = vec![
+            WordMatch {
+                start: 0,
+                end: 4,
+                parts: vec![1..2],
+            },
+            WordMatch {
+                start: 4,
+                end: 8,
+                parts: vec![5..6, 7..8],
+            },
+        ];
+        assert_eq!(matches, expected);
+
+        let matcher = WordMatcher::new(
+            &[],
+            &["e[.]g.".to_string()],
+            false,
+            WordNormalization::FoldWidth,
+            WordMatcherKind::Auto,
+        )
+        .unwrap();
+        let text = "ｅ．ｇ．";
+        let matches: Vec<_> = matcher.iter(text, 0..text.len()).collect();
+        let expected = vec![WordMatch {
+            start: 0,
+            end: 12,
+            parts: vec![3..6],
+        }];
+        assert_eq!(matches, expected);
+    }
+
+    #[test]
+    fn test_word_boundary() {
+        let words = vec![
+            (
+                "St.".to_string(),
+                WordBoundary::NonAlphanumeric,
+                WordBoundary::Any,
+            ),
+            (
+                "ab".to_string(),
+                WordBoundary::Any,
+                WordBoundary::Class("[^c]".to_string()),
+            ),
+        ];
+        let matcher = WordMatcher::new(
+            &words,
+            &[],
+            false,
+            WordNormalization::None,
+            WordMatcherKind::Auto,
+        )
+        .unwrap();
+        let text = "1st. St. St.abcabd";
+        let matches: Vec<_> = matcher.iter(text, 0..text.len()).collect();
+        let expected = vec![
+            WordMatch {
+                start: 5,
+                end: 8,
+                parts: vec![],
+            },
+            WordMatch {
+                start: 9,
+                end: 12,
+                parts: vec![],
+            },
+            WordMatch {
+                start: 15,
+                end: 17,
+                parts: vec![],
+            },
+        ];
+        assert_eq!(matches, expected);
+        // The adjacent characters outside the range are also checked.
+        assert_eq!(matcher.iter(text, 12..14).count(), 0);
+        assert_eq!(matcher.iter("xSt.", 1..4).count(), 0);
+
+        let words = vec![(
+            "a".to_string(),
+            WordBoundary::Class("a-z".to_string()),
+            WordBoundary::Any,
+        )];
+        assert!(WordMatcher::new(
+            &words,
+            &[],
+            false,
+            WordNormalization::None,
+            WordMatcherKind::Auto
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_delimiter_regex() {
+        let regexes = vec![
+            (Regex::new(r"(\. )[A-Z]").unwrap(), true),
+            (Regex::new(r"\n+").unwrap(), false),
+            (Regex::new(r"x(y)?").unwrap(), false),
+        ];
+        let matcher =
+            DelimiterMatcher::new(&[". ", "!"], &["\n"], &[], &[], regexes, &[], &[]).unwrap();
+        let text = "a. b. C\n\nD!x";
+        let matches: Vec<_> = matcher
+            .iter(text, 0..text.len())
+            .map(|m| (m.start, m.end, m.is_in_delimiter))
+            .collect();
+        let expected = vec![
+            (1, 3, true),
+            (4, 6, true),
+            (7, 9, false),
+            (10, 11, true),
+            (12, 12, false),
+        ];
+        assert_eq!(matches, expected);
+
+        // Regex patterns are matched with the context after the range.
+        let matches: Vec<_> = matcher.iter(text, 3..6).map(|m| (m.start, m.end)).collect();
+        assert_eq!(matches, vec![(4, 6), (6, 6)]);
+    }
+
+    #[test]
+    fn test_bounded_delimiter() {
+        let in_bounded = vec![
+            (
+                "。」".to_string(),
+                WordBoundary::Any,
+                WordBoundary::Class("[^a]".to_string()),
+            ),
+            ("。".to_string(), WordBoundary::Any, WordBoundary::Any),
+        ];
+        let ex_bounded = vec![(
+            "\n".to_string(),
+            WordBoundary::Class("[。」]".to_string()),
+            WordBoundary::Any,
+        )];
+        let matcher =
+            DelimiterMatcher::new(&["！"], &[], &in_bounded, &ex_bounded, vec![], &[], &[])
+                .unwrap();
+        let text = "x。」y。」a\nz！\nw。\n";
+        let matches: Vec<_> = matcher
+            .iter(text, 0..text.len())
+            .map(|m| (m.start, m.end, m.is_in_delimiter))
+            .collect();
+        let expected = vec![
+            (1, 7, true),
+            (8, 11, true),
+            (17, 20, true),
+            (22, 25, true),
+            (25, 26, false),
+            (26, 26, false),
+        ];
+        assert_eq!(matches, expected);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_occurs_across() {
+        let matcher =
+            DelimiterMatcher::new(&["。", "。」"], &["\r\n"], &[], &[], vec![], &[], &[]).unwrap();
+        let text = "あ。」\r\n";
+        assert!(!matcher.occurs_across(text, 3));
+        assert!(matcher.occurs_across(text, 6));
+        assert!(!matcher.occurs_across(text, 9));
+        assert!(matcher.occurs_across(text, 10));
+        assert!(!matcher.occurs_across(text, 11));
+
+        let bounded = vec![(
+            "。」".to_string(),
+            WordBoundary::Any,
+            WordBoundary::Class("[a]".to_string()),
+        )];
+        let matcher = DelimiterMatcher::new(&["。"], &[], &bounded, &[], vec![], &[], &[]).unwrap();
+        assert!(matcher.occurs_across("あ。」", 6));
+        assert!(!matcher.occurs_across("あ。」", 3));
+
+        let matcher = QuoteMatcher::new(&[("<", ">"), ("<<", ">>")], &["\n\n"]).unwrap();
+        assert!(matcher.occurs_across("a<<b", 2));
+        assert!(!matcher.occurs_across("a<b", 2));
+        assert!(matcher.occurs_across("a\n\n", 2));
+
+        let matcher = WordMatcher::new(
+            &words(&["ab", "bcd"]),
+            &[],
+            false,
+            WordNormalization::None,
+            WordMatcherKind::Auto,
+        )
+        .unwrap();
+        assert!(matcher.occurs_across("abcd", 1));
+        assert!(matcher.occurs_across("abcd", 3));
+        assert!(!matcher.occurs_across("abxd", 3));
+
+        let matcher = WordMatcher::new(
+            &words(&["ab."]),
+            &[],
+            true,
+            WordNormalization::FoldWidth,
+            WordMatcherKind::Auto,
+        )
+        .unwrap();
+        assert!(matcher.occurs_across("xＡＢ．", 4));
+        assert!(matcher.occurs_across("xＡＢ．", 7));
+        assert!(!matcher.occurs_across("xＡＢ．", 10));
+        assert!(!matcher.occurs_across("xＡＢ，", 4));
     }
 }
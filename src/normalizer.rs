@@ -0,0 +1,246 @@
+//! Normalization of texts for matching no-break words,
+//! keeping the mapping to the positions in the original text.
+use std::ops::Range;
+
+use crate::segmenter::builder::WordNormalization;
+
+/// Maximum number of characters following a base character in a normalization unit,
+/// which is the limit of non-starters in the Stream-Safe Text Format of UAX #15.
+#[cfg(feature = "nfkc")]
+const MAX_NON_STARTERS: usize = 30;
+
+/// Half-width katakana from U+FF61 to U+FF9F in their full-width forms.
+const FULL_WIDTH_KATAKANA: [char; 63] = [
+    '。', '「', '」', '、', '・', 'ヲ', 'ァ', 'ィ', 'ゥ', 'ェ', 'ォ', 'ャ', 'ュ', 'ョ', 'ッ', 'ー',
+    'ア', 'イ', 'ウ', 'エ', 'オ', 'カ', 'キ', 'ク', 'ケ', 'コ', 'サ', 'シ', 'ス', 'セ', 'ソ', 'タ',
+    'チ', 'ツ', 'テ', 'ト', 'ナ', 'ニ', 'ヌ', 'ネ', 'ノ', 'ハ', 'ヒ', 'フ', 'ヘ', 'ホ', 'マ', 'ミ',
+    'ム', 'メ', 'モ', 'ヤ', 'ユ', 'ヨ', 'ラ', 'リ', 'ル', 'レ', 'ロ', 'ワ', 'ン', '゛', '゜',
+];
+
+/// Text normalized in units of a character with its following combining characters.
+pub struct NormalizedText {
+    text: String,
+    // Pairs of the normalized and original positions at the boundaries of units.
+    boundaries: Vec<(usize, usize)>,
+}
+
+impl NormalizedText {
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// Converts a range in the normalized text into the range in the original text,
+    /// or returns `None` if the range is not aligned to the units.
+    pub fn original_range(&self, range: Range<usize>) -> Option<Range<usize>> {
+        let original_pos = |pos| {
+            self.boundaries
+                .binary_search_by_key(&pos, |&(normalized, _)| normalized)
+                .ok()
+                .map(|i| self.boundaries[i].1)
+        };
+        Some(original_pos(range.start)?..original_pos(range.end)?)
+    }
+}
+
+/// Normalizes `text` unit by unit, where a unit is a character with its following
+/// characters to be composed, so that the units can be mapped to the original text.
+pub fn normalize(text: &str, normalization: WordNormalization) -> NormalizedText {
+    let mut normalized = NormalizedText {
+        text: String::with_capacity(text.len()),
+        boundaries: vec![(0, 0)],
+    };
+    let mut start = 0;
+    while start < text.len() {
+        let end = unit_end(text, start, normalization);
+        push_unit(&text[start..end], normalization, &mut normalized.text);
+        normalized.boundaries.push((normalized.text.len(), end));
+        start = end;
+    }
+    normalized
+}
+
+/// Returns the maximum length of an original text whose normalized text has `len` bytes.
+pub const fn max_original_len(len: usize, normalization: WordNormalization) -> usize {
+    match normalization {
+        WordNormalization::None => len,
+        // A full-width character of three bytes is folded into an ASCII character.
+        WordNormalization::FoldWidth => len * 3,
+        // Each unit, which has at most four bytes per character, is normalized
+        // into at least one byte.
+        #[cfg(feature = "nfkc")]
+        WordNormalization::Nfkc => len * (MAX_NON_STARTERS + 1) * 4,
+    }
+}
+
+/// Moves `pos` backward to the nearest character boundary that can start a unit.
+pub fn unit_start(text: &str, mut pos: usize, normalization: WordNormalization) -> usize {
+    while !text.is_char_boundary(pos) {
+        pos -= 1;
+    }
+    let (max_steps, joins_previous): (usize, fn(char) -> bool) = match normalization {
+        WordNormalization::None => (0, |_| false),
+        WordNormalization::FoldWidth => (1, is_sound_mark),
+        #[cfg(feature = "nfkc")]
+        WordNormalization::Nfkc => (MAX_NON_STARTERS, is_non_starter),
+    };
+    for _ in 0..max_steps {
+        match text[pos..].chars().next() {
+            Some(c) if pos != 0 && joins_previous(c) => {
+                pos -= text[..pos].chars().next_back().map_or(0, char::len_utf8);
+            }
+            _ => break,
+        }
+    }
+    pos
+}
+
+/// Moves `pos` forward to the nearest character boundary.
+#[cfg(feature = "rayon")]
+pub const fn char_end(text: &str, mut pos: usize) -> usize {
+    while !text.is_char_boundary(pos) {
+        pos += 1;
+    }
+    pos
+}
+
+fn unit_end(text: &str, start: usize, normalization: WordNormalization) -> usize {
+    let mut chars = text[start..].chars();
+    let first = chars.next().unwrap();
+    let mut end = start + first.len_utf8();
+    match normalization {
+        WordNormalization::None => {}
+        WordNormalization::FoldWidth => {
+            if let Some(mark) = chars.next().filter(|&c| is_sound_mark(c)) {
+                // A sound mark after a character other than katakana is not composed.
+                if compose_sound_mark(fold_width(first), mark).is_some() {
+                    end += mark.len_utf8();
+                }
+            }
+        }
+        #[cfg(feature = "nfkc")]
+        WordNormalization::Nfkc => {
+            for c in chars.take(MAX_NON_STARTERS) {
+                if !is_non_starter(c) {
+                    break;
+                }
+                end += c.len_utf8();
+            }
+        }
+    }
+    end
+}
+
+fn push_unit(unit: &str, normalization: WordNormalization, output: &mut String) {
+    match normalization {
+        WordNormalization::None => output.push_str(unit),
+        WordNormalization::FoldWidth => {
+            let mut chars = unit.chars();
+            let first = fold_width(chars.next().unwrap());
+            let composed = chars
+                .next()
+                .and_then(|mark| compose_sound_mark(first, mark));
+            output.push(composed.unwrap_or(first));
+        }
+        #[cfg(feature = "nfkc")]
+        WordNormalization::Nfkc => {
+            use unicode_normalization::UnicodeNormalization;
+            output.extend(unit.nfkc());
+        }
+    }
+}
+
+/// Checks if `c` is a half-width sound mark composed with the preceding katakana.
+const fn is_sound_mark(c: char) -> bool {
+    matches!(c, '\u{ff9e}' | '\u{ff9f}')
+}
+
+/// Checks if `c` is composed with the preceding character in NFKC.
+#[cfg(feature = "nfkc")]
+fn is_non_starter(c: char) -> bool {
+    // Half-width sound marks are also decomposed into combining characters.
+    let mut first = None;
+    unicode_normalization::char::decompose_compatible(c, |d| {
+        first.get_or_insert(d);
+    });
+    first.is_some_and(|d| unicode_normalization::char::canonical_combining_class(d) != 0)
+}
+
+/// Folds full-width ASCII variants into ASCII characters, the ideographic space into
+/// the ASCII space, and half-width katakana into full-width ones.
+const fn fold_width(c: char) -> char {
+    match c {
+        '\u{3000}' => ' ',
+        '\u{ff01}'..='\u{ff5e}' => char::from_u32(c as u32 - 0xfee0).unwrap(),
+        '\u{ff61}'..='\u{ff9f}' => FULL_WIDTH_KATAKANA[(c as u32 - 0xff61) as usize],
+        _ => c,
+    }
+}
+
+/// Composes a full-width katakana with a half-width (semi-)voiced sound mark,
+/// e.g., `カ` and `ﾞ` into `ガ`.
+const fn compose_sound_mark(kana: char, mark: char) -> Option<char> {
+    let code = kana as u32;
+    let composed = match mark {
+        '\u{ff9e}' => match code {
+            // ウ
+            0x30a6 => 0x30f4,
+            // From カ to チ
+            0x30ab..=0x30c1 if !code.is_multiple_of(2) => code + 1,
+            // ツ, テ, and ト
+            0x30c4..=0x30c8 if code.is_multiple_of(2) => code + 1,
+            // From ハ to ホ
+            0x30cf..=0x30db if (code - 0x30cf).is_multiple_of(3) => code + 1,
+            // ワ and ヲ
+            0x30ef | 0x30f2 => code + 8,
+            _ => return None,
+        },
+        '\u{ff9f}' => match code {
+            0x30cf..=0x30db if (code - 0x30cf).is_multiple_of(3) => code + 2,
+            _ => return None,
+        },
+        _ => return None,
+    };
+    char::from_u32(composed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fold_width() {
+        let text = "Ｍｒ．　ﾃﾞｨｽﾞﾆｰ";
+        let normalized = normalize(text, WordNormalization::FoldWidth);
+        assert_eq!(normalized.as_str(), "Mr. ディズニー");
+        // "ﾃﾞ" is a unit.
+        assert_eq!(normalized.original_range(4..7), Some(12..18));
+        assert_eq!(normalized.original_range(1..4), Some(3..12));
+        assert_eq!(normalized.original_range(5..7), None);
+    }
+
+    #[test]
+    fn test_fold_width_sound_marks() {
+        let normalized = normalize("ﾊﾟｳﾞｧﾞ", WordNormalization::FoldWidth);
+        assert_eq!(normalized.as_str(), "パヴァ゛");
+    }
+
+    #[cfg(feature = "nfkc")]
+    #[test]
+    fn test_nfkc() {
+        let text = "ｶﾞ㍻e\u{301}";
+        let normalized = normalize(text, WordNormalization::Nfkc);
+        assert_eq!(normalized.as_str(), "ガ平成é");
+        assert_eq!(normalized.original_range(0..3), Some(0..6));
+        assert_eq!(normalized.original_range(3..9), Some(6..9));
+        assert_eq!(normalized.original_range(3..6), None);
+        assert_eq!(normalized.original_range(9..11), Some(9..12));
+    }
+
+    #[test]
+    fn test_unit_start() {
+        let text = "aｶﾞ";
+        assert_eq!(unit_start(text, 5, WordNormalization::FoldWidth), 1);
+        assert_eq!(unit_start(text, 4, WordNormalization::FoldWidth), 1);
+        assert_eq!(unit_start(text, 4, WordNormalization::None), 4);
+    }
+}
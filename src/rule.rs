@@ -1,18 +1,165 @@
+//! Segmentation rules defined in the TOML format.
 use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
 
-use serde_derive::Deserialize;
+use serde_derive::{Deserialize, Serialize};
 
-use crate::errors::Result;
+use crate::errors::{EasySegmenterError, Result};
+use crate::segmenter::builder::{
+    QuoteMismatchPolicy, QuotePolicy, SymmetricQuoteRule, TrimPolicy, WordBoundary,
+    WordMatcherKind, WordNormalization,
+};
+use crate::template;
+
+/// The prefix to refer to built-in templates in `extends`.
+pub const TEMPLATE_PREFIX: &str = "template:";
 
 /// Configure of segmentation rules.
-#[derive(Deserialize, Debug, PartialEq, Eq, Default)]
+///
+/// This struct is usually deserialized from a rule file and compiled through
+/// [`SegmenterBuilder::from_rule_config`](crate::SegmenterBuilder::from_rule_config).
+#[derive(Deserialize, Serialize, Debug, PartialEq, Eq, Default, Clone)]
 #[serde(default)]
-struct RuleConfig {
-    in_delimiters: Vec<String>,
-    ex_delimiters: Vec<String>,
-    quotes: Vec<String>,
-    words: Vec<String>,
-    regex: BTreeMap<String, String>,
+pub struct RuleConfig {
+    /// Rule files or built-in templates to be extended.
+    /// See [`Self::resolve`] for the details.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub extends: Vec<String>,
+    /// Delimiters included in resulting sentences.
+    pub in_delimiters: Vec<String>,
+    /// Delimiters excluded in resulting sentences.
+    pub ex_delimiters: Vec<String>,
+    /// Inclusive delimiters whose adjacent runs such as `！？` are merged into one.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub mergeable_delimiters: Vec<String>,
+    /// Closing brackets and marks attached to the preceding sentence
+    /// when they immediately follow an inclusive delimiter.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub trailing_attachers: Vec<String>,
+    /// Quotations, each of which consists of an opening and a closing character.
+    /// The two characters of a symmetric quotation are the same, e.g., `"\"\""`.
+    pub quotes: Vec<String>,
+    /// Quotations whose opening and closing marks are arbitrary strings,
+    /// e.g., `["<<", ">>"]` and `["<q>", "</q>"]`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub quote_pairs: Vec<(String, String)>,
+    /// Words that should not be broken.
+    pub words: Vec<String>,
+    /// Words whose parts enclosed in brackets such as `e[.]g.` should not be broken.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub word_patterns: Vec<String>,
+    /// Words to be removed from the extended rules.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub remove_words: Vec<String>,
+    /// Delimiters to be removed from the extended rules.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub remove_delimiters: Vec<String>,
+    /// Words after which texts are always broken, even inside quotations and no-break words.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub force_break_words: Vec<String>,
+    /// Maximum nested level of quotations.
+    /// If it is not specified, the default value of the builder is used.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_quote_level: Option<usize>,
+    /// Maximum length of quotations in characters.
+    /// If it is not specified, the default value of the builder is used.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_quote_len: Option<usize>,
+    /// Delimiters at which all open quotations are discarded.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub quote_reset_delimiters: Vec<String>,
+    /// Policy for mismatched closing quotation marks,
+    /// which is `"skip"`, `"pop_to_match"`, or `"reset"`.
+    /// If it is not specified, the default value of the builder is used.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quote_mismatch_policy: Option<QuoteMismatchPolicy>,
+    /// Rule for symmetric quotation marks, which is `"toggle"` or `"word_boundary"`.
+    /// If it is not specified, the default value of the builder is used.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub symmetric_quote_rule: Option<SymmetricQuoteRule>,
+    /// Whether no-break words are matched ignoring ASCII case.
+    /// If it is not specified, the default value of the builder is used.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub word_ascii_case_insensitive: Option<bool>,
+    /// Normalization for matching no-break words, which is `"none"`, `"fold_width"`,
+    /// or `"nfkc"` (with the `nfkc` feature).
+    /// If it is not specified, the default value of the builder is used.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub word_normalization: Option<WordNormalization>,
+    /// Automaton to match no-break words, which is `"auto"`, `"dfa"`, or `"nfa"`.
+    /// If it is not specified, the default value of the builder is used.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub word_matcher_kind: Option<WordMatcherKind>,
+    /// Characters trimmed from both ends of each sentence, which is `"none"`, `"ascii"`,
+    /// `"unicode"`, or a character class such as `"[ 　]"`.
+    /// If it is not specified, the default value of the builder is used.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trim_policy: Option<TrimPolicy>,
+    /// Whether sentences that are empty after trimming are dropped.
+    /// If it is not specified, the default value of the builder is used.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub drop_empty_sentences: Option<bool>,
+    /// Policies to break sentences inside quotations, keyed by the opening marks,
+    /// which is `"protect"`, `"split_inside"`, or `{ split_if_longer_than = N }`.
+    #[serde(
+        skip_serializing_if = "BTreeMap::is_empty",
+        serialize_with = "toml::ser::tables_last"
+    )]
+    pub quote_policies: BTreeMap<String, QuotePolicy>,
+    /// Named regex patterns that should not be broken.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub regex: BTreeMap<String, String>,
+    /// Named regex patterns for inclusive delimiters,
+    /// whose first captured group, or whole match if no group, is the delimiter.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub in_delimiter_regex: BTreeMap<String, String>,
+    /// Named regex patterns for exclusive delimiters,
+    /// whose first captured group, or whole match if no group, is the delimiter.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub ex_delimiter_regex: BTreeMap<String, String>,
+    /// Named regex patterns that always break texts at the start of each captured group,
+    /// or at the end of the match if the pattern has no group.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub force_break_regex: BTreeMap<String, String>,
+    /// Words that should not be broken only if the adjacent characters satisfy the conditions.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub bounded_words: Vec<BoundedWords>,
+    /// Delimiters that break texts only if the adjacent characters satisfy the conditions.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub bounded_delimiters: Vec<BoundedDelimiters>,
+}
+
+/// Group of words that should not be broken only if the adjacent characters
+/// satisfy the conditions.
+#[derive(Deserialize, Serialize, Debug, PartialEq, Eq, Clone)]
+pub struct BoundedWords {
+    /// Words in the group.
+    pub words: Vec<String>,
+    /// Condition on the character preceding the words.
+    #[serde(default)]
+    pub before: WordBoundary,
+    /// Condition on the character following the words.
+    #[serde(default)]
+    pub after: WordBoundary,
+}
+
+/// Group of delimiters that break texts only if the adjacent characters
+/// satisfy the conditions.
+#[derive(Deserialize, Serialize, Debug, PartialEq, Eq, Clone)]
+pub struct BoundedDelimiters {
+    /// Delimiters included in resulting sentences.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub in_delimiters: Vec<String>,
+    /// Delimiters excluded in resulting sentences.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ex_delimiters: Vec<String>,
+    /// Condition on the character preceding the delimiters.
+    #[serde(default)]
+    pub before: WordBoundary,
+    /// Condition on the character following the delimiters.
+    #[serde(default)]
+    pub after: WordBoundary,
 }
 
 impl RuleConfig {
@@ -21,13 +168,46 @@ impl RuleConfig {
     /// # Format
     ///
     /// ```toml
+    /// extends = ["base.toml", "template:ja"]
     /// in_delimiters = ["。", "．"]
     /// ex_delimiters = ["\n", "\r\n", "\r"]
+    /// mergeable_delimiters = ["。", "．"]
+    /// trailing_attachers = ["」", "）"]
     /// quotes = ["「」", "（）"]
+    /// quote_pairs = [["<<", ">>"], ["<q>", "</q>"]]
     /// words = ["モーニング娘。"]
+    /// remove_words = ["娘。"]
+    /// remove_delimiters = ["！"]
+    /// force_break_words = ["\n\n"]
+    /// max_quote_level = 3
+    /// max_quote_len = 1000
+    /// quote_reset_delimiters = ["\n\n"]
+    /// quote_mismatch_policy = "pop_to_match"
+    /// symmetric_quote_rule = "word_boundary"
+    /// word_ascii_case_insensitive = true
+    /// word_normalization = "fold_width"
+    /// word_matcher_kind = "nfa"
+    /// trim_policy = "unicode"
+    /// drop_empty_sentences = true
+    /// [quote_policies]
+    /// "「" = "split_inside"
+    /// "（" = { split_if_longer_than = 100 }
     /// [regex]
     /// decimal_point = '\d(．)\d'
     /// dot_sequence = '(。{2,})。'
+    /// [in_delimiter_regex]
+    /// period = '(\. )[A-Z]'
+    /// [ex_delimiter_regex]
+    /// blank_lines = '\n{2,}'
+    /// [force_break_regex]
+    /// article = '\n(第\d+条)'
+    /// [[bounded_words]]
+    /// words = ["St.", "Mr."]
+    /// before = "non_alphanumeric"
+    /// after = "[^a-z]"
+    /// [[bounded_delimiters]]
+    /// in_delimiters = ["。"]
+    /// after = "[^」）]"
     /// ```
     ///
     /// # Errors
@@ -39,6 +219,223 @@ impl RuleConfig {
     {
         Ok(toml::from_str(toml_str.as_ref())?)
     }
+
+    /// Reads a file in the TOML format into a [`RuleConfig`].
+    ///
+    /// # Errors
+    ///
+    /// [`std::io::Error`] will be reported if the file cannot be read, and
+    /// [`toml::de::Error`] will be reported if the deserialization fails.
+    pub fn from_toml_file<P>(path: P) -> Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        Self::from_toml_str(fs::read_to_string(path)?)
+    }
+
+    /// Resolves `extends`, `remove_words`, and `remove_delimiters`,
+    /// returning the merged rules without those keys.
+    ///
+    /// Each entry in `extends` is either a path to a rule file,
+    /// which is relative to `base_dir`, or the name of a built-in template
+    /// with the prefix [`TEMPLATE_PREFIX`] such as `"template:ja"`
+    /// (see [`template::by_name`] for the available names).
+    /// Extended rule files can also have `extends` recursively.
+    ///
+    /// The rules are merged as follows:
+    ///
+    /// 1. The extended rules are merged in the order of `extends`.
+    /// 2. `remove_words` and `remove_delimiters` are removed from the merged rules.
+    ///    Delimiters are removed from `in_delimiters`, `ex_delimiters`,
+    ///    `mergeable_delimiters`, `quote_reset_delimiters`, and `bounded_delimiters`,
+    ///    and regex delimiters whose patterns equal them are also removed.
+    ///    Words are removed from `words`, `word_patterns`, and `bounded_words`.
+    /// 3. The own rules are merged.
+    ///
+    /// In each merge, lists are concatenated while skipping entries that already appear,
+    /// and quotation policies with the same opening mark, regex patterns with the same name,
+    /// and the other options such as `max_quote_level` are overwritten.
+    ///
+    /// # Errors
+    ///
+    /// An error will arise with the key of the invalid rule when
+    ///  - an extended rule file cannot be loaded,
+    ///  - a template is not found,
+    ///  - rule files are extended circularly, or
+    ///  - an entry to be removed is not defined in the extended rules.
+    pub fn resolve<P>(self, base_dir: P) -> Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        self.resolve_inner(base_dir.as_ref(), &mut vec![])
+    }
+
+    fn resolve_inner(mut self, base_dir: &Path, visiting: &mut Vec<PathBuf>) -> Result<Self> {
+        let mut merged = Self::default();
+        for (i, parent) in std::mem::take(&mut self.extends).iter().enumerate() {
+            let config = if let Some(name) = parent.strip_prefix(TEMPLATE_PREFIX) {
+                template::by_name(name)
+                    .ok_or_else(|| rule_error("extends", i, parent, "The template is not found."))?
+                    .to_rule_config()
+            } else {
+                let path = base_dir.join(parent);
+                let canonical = path
+                    .canonicalize()
+                    .map_err(|e| rule_error("extends", i, parent, e.to_string()))?;
+                if visiting.contains(&canonical) {
+                    return Err(rule_error(
+                        "extends",
+                        i,
+                        parent,
+                        "The rule file is extended circularly.",
+                    ));
+                }
+                visiting.push(canonical);
+                let config = Self::from_toml_file(&path)
+                    .and_then(|c| c.resolve_inner(path.parent().unwrap_or(base_dir), visiting))
+                    .map_err(|e| rule_error("extends", i, parent, e.to_string()))?;
+                visiting.pop();
+                config
+            };
+            merged.merge(config);
+        }
+        for (i, word) in std::mem::take(&mut self.remove_words).iter().enumerate() {
+            let mut removed = remove_entry(&mut merged.words, word);
+            removed |= remove_entry(&mut merged.word_patterns, word);
+            for group in &mut merged.bounded_words {
+                removed |= remove_entry(&mut group.words, word);
+            }
+            merged.bounded_words.retain(|g| !g.words.is_empty());
+            if !removed {
+                return Err(rule_error(
+                    "remove_words",
+                    i,
+                    word,
+                    "The entry is not defined in the extended rules.",
+                ));
+            }
+        }
+        for (i, delimiter) in std::mem::take(&mut self.remove_delimiters)
+            .iter()
+            .enumerate()
+        {
+            let mut removed = remove_entry(&mut merged.in_delimiters, delimiter);
+            removed |= remove_entry(&mut merged.ex_delimiters, delimiter);
+            removed |= remove_entry(&mut merged.mergeable_delimiters, delimiter);
+            removed |= remove_entry(&mut merged.quote_reset_delimiters, delimiter);
+            removed |= remove_pattern(&mut merged.in_delimiter_regex, delimiter);
+            removed |= remove_pattern(&mut merged.ex_delimiter_regex, delimiter);
+            for group in &mut merged.bounded_delimiters {
+                removed |= remove_entry(&mut group.in_delimiters, delimiter);
+                removed |= remove_entry(&mut group.ex_delimiters, delimiter);
+            }
+            merged
+                .bounded_delimiters
+                .retain(|g| !g.in_delimiters.is_empty() || !g.ex_delimiters.is_empty());
+            if !removed {
+                return Err(rule_error(
+                    "remove_delimiters",
+                    i,
+                    delimiter,
+                    "The entry is not defined in the extended rules.",
+                ));
+            }
+        }
+        merged.merge(self);
+        Ok(merged)
+    }
+
+    fn merge(&mut self, other: Self) {
+        merge_entries(&mut self.in_delimiters, other.in_delimiters);
+        merge_entries(&mut self.ex_delimiters, other.ex_delimiters);
+        merge_entries(&mut self.mergeable_delimiters, other.mergeable_delimiters);
+        merge_entries(&mut self.trailing_attachers, other.trailing_attachers);
+        merge_entries(&mut self.quotes, other.quotes);
+        merge_entries(&mut self.quote_pairs, other.quote_pairs);
+        merge_entries(&mut self.words, other.words);
+        merge_entries(&mut self.word_patterns, other.word_patterns);
+        merge_entries(&mut self.bounded_words, other.bounded_words);
+        merge_entries(&mut self.bounded_delimiters, other.bounded_delimiters);
+        merge_entries(&mut self.force_break_words, other.force_break_words);
+        merge_entries(
+            &mut self.quote_reset_delimiters,
+            other.quote_reset_delimiters,
+        );
+        if other.max_quote_level.is_some() {
+            self.max_quote_level = other.max_quote_level;
+        }
+        if other.max_quote_len.is_some() {
+            self.max_quote_len = other.max_quote_len;
+        }
+        if other.quote_mismatch_policy.is_some() {
+            self.quote_mismatch_policy = other.quote_mismatch_policy;
+        }
+        if other.symmetric_quote_rule.is_some() {
+            self.symmetric_quote_rule = other.symmetric_quote_rule;
+        }
+        if other.word_ascii_case_insensitive.is_some() {
+            self.word_ascii_case_insensitive = other.word_ascii_case_insensitive;
+        }
+        if other.word_normalization.is_some() {
+            self.word_normalization = other.word_normalization;
+        }
+        if other.word_matcher_kind.is_some() {
+            self.word_matcher_kind = other.word_matcher_kind;
+        }
+        if other.trim_policy.is_some() {
+            self.trim_policy = other.trim_policy;
+        }
+        if other.drop_empty_sentences.is_some() {
+            self.drop_empty_sentences = other.drop_empty_sentences;
+        }
+        self.quote_policies.extend(other.quote_policies);
+        self.regex.extend(other.regex);
+        self.in_delimiter_regex.extend(other.in_delimiter_regex);
+        self.ex_delimiter_regex.extend(other.ex_delimiter_regex);
+        self.force_break_regex.extend(other.force_break_regex);
+    }
+
+    /// Serializes the [`RuleConfig`] into a string in the TOML format.
+    ///
+    /// # Errors
+    ///
+    /// [`toml::ser::Error`] will be reported if the serialization fails.
+    pub fn to_toml_string(&self) -> Result<String> {
+        Ok(toml::to_string(self)?)
+    }
+}
+
+fn merge_entries<T>(entries: &mut Vec<T>, others: Vec<T>)
+where
+    T: PartialEq,
+{
+    for other in others {
+        if !entries.contains(&other) {
+            entries.push(other);
+        }
+    }
+}
+
+fn remove_entry(entries: &mut Vec<String>, entry: &str) -> bool {
+    let len = entries.len();
+    entries.retain(|e| e != entry);
+    entries.len() != len
+}
+
+fn remove_pattern(patterns: &mut BTreeMap<String, String>, pattern: &str) -> bool {
+    let len = patterns.len();
+    patterns.retain(|_, p| p != pattern);
+    patterns.len() != len
+}
+
+pub(crate) fn rule_error<M>(key: &str, index: usize, entry: &str, msg: M) -> EasySegmenterError
+where
+    M: AsRef<str>,
+{
+    EasySegmenterError::input(format!(
+        "Invalid rule in {key}[{index}] = {entry:?}: {}",
+        msg.as_ref()
+    ))
 }
 
 #[cfg(test)]
@@ -56,9 +453,7 @@ mod tests {
         let expected = RuleConfig {
             in_delimiters: vec!["。".to_string()],
             ex_delimiters: vec!["\n".to_string(), "\r\n".to_string(), "\r".to_string()],
-            quotes: vec![],
-            words: vec![],
-            regex: BTreeMap::new(),
+            ..Default::default()
         };
         assert_eq!(rule_set, expected);
     }
@@ -69,7 +464,9 @@ mod tests {
             in_delimiters = ["。", "．"]
             ex_delimiters = ["\n", "\r\n", "\r"]
             quotes = ["「」", "（）"]
+            quote_pairs = [["<<", ">>"]]
             words = ["モーニング娘。"]
+            max_quote_level = 2
             [regex]
             decimal_point = '\d(．)\d'
             dot_sequence = '(。{2,})。'
@@ -80,11 +477,14 @@ mod tests {
             in_delimiters: vec!["。".to_string(), "．".to_string()],
             ex_delimiters: vec!["\n".to_string(), "\r\n".to_string(), "\r".to_string()],
             quotes: vec!["「」".to_string(), "（）".to_string()],
+            quote_pairs: vec![("<<".to_string(), ">>".to_string())],
             words: vec!["モーニング娘。".to_string()],
+            max_quote_level: Some(2),
             regex: BTreeMap::from([
                 ("decimal_point".to_string(), r"\d(．)\d".to_string()),
                 ("dot_sequence".to_string(), r"(。{2,})。".to_string()),
             ]),
+            ..Default::default()
         };
         assert_eq!(rule_set, expected);
     }
@@ -94,13 +494,7 @@ mod tests {
         let toml_str = "";
 
         let rule_set = RuleConfig::from_toml_str(toml_str).unwrap();
-        let expected = RuleConfig {
-            in_delimiters: vec![],
-            ex_delimiters: vec![],
-            quotes: vec![],
-            words: vec![],
-            regex: BTreeMap::new(),
-        };
+        let expected = RuleConfig::default();
         assert_eq!(rule_set, expected);
     }
 
@@ -113,10 +507,7 @@ mod tests {
         let rule_set = RuleConfig::from_toml_str(toml_str).unwrap();
         let expected = RuleConfig {
             in_delimiters: vec!["。".to_string()],
-            ex_delimiters: vec![],
-            quotes: vec![],
-            words: vec![],
-            regex: BTreeMap::new(),
+            ..Default::default()
         };
         assert_eq!(rule_set, expected);
     }
@@ -128,4 +519,267 @@ mod tests {
         "#;
         assert!(RuleConfig::from_toml_str(toml_str).is_err());
     }
+
+    #[test]
+    fn test_to_toml_string() {
+        let rule_set = RuleConfig {
+            in_delimiters: vec!["。".to_string()],
+            ex_delimiters: vec!["\n".to_string(), "\r\n".to_string()],
+            quotes: vec!["「」".to_string()],
+            max_quote_level: Some(2),
+            regex: BTreeMap::from([("decimal_point".to_string(), r"\d(．)\d".to_string())]),
+            ..Default::default()
+        };
+        let toml_str = rule_set.to_toml_string().unwrap();
+        let expected = r#"in_delimiters = ["。"]
+ex_delimiters = ["\n", "\r\n"]
+quotes = ["「」"]
+words = []
+max_quote_level = 2
+
+[regex]
+decimal_point = "\\d(．)\\d"
+"#;
+        assert_eq!(toml_str, expected);
+        assert_eq!(RuleConfig::from_toml_str(toml_str).unwrap(), rule_set);
+    }
+
+    #[test]
+    fn test_resolve_extends_files() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("base.toml"),
+            r#"
+            in_delimiters = ["。"]
+            ex_delimiters = ["\n"]
+            words = ["モーニング娘。", "カントリー娘。"]
+            max_quote_level = 3
+            [regex]
+            decimal_point = '\d(．)\d'
+            "#,
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("middle.toml"),
+            r#"
+            extends = ["base.toml"]
+            quotes = ["「」"]
+            [regex]
+            decimal_point = '\d(．|\.)\d'
+            "#,
+        )
+        .unwrap();
+        let toml_str = r#"
+            extends = ["middle.toml"]
+            in_delimiters = ["．", "。"]
+            words = ["ミニモニ。"]
+            remove_words = ["モーニング娘。"]
+            remove_delimiters = ["\n"]
+            max_quote_level = 2
+        "#;
+        let rule_set = RuleConfig::from_toml_str(toml_str)
+            .unwrap()
+            .resolve(&dir)
+            .unwrap();
+        let expected = RuleConfig {
+            in_delimiters: vec!["。".to_string(), "．".to_string()],
+            quotes: vec!["「」".to_string()],
+            words: vec!["カントリー娘。".to_string(), "ミニモニ。".to_string()],
+            max_quote_level: Some(2),
+            regex: BTreeMap::from([("decimal_point".to_string(), r"\d(．|\.)\d".to_string())]),
+            ..Default::default()
+        };
+        assert_eq!(rule_set, expected);
+    }
+
+    #[test]
+    fn test_resolve_bounded_words() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("base.toml"),
+            r#"
+            [[bounded_words]]
+            words = ["St.", "Mr."]
+            before = "non_alphanumeric"
+            [[bounded_words]]
+            words = ["No."]
+            after = "[0-9 ]"
+            "#,
+        )
+        .unwrap();
+        let toml_str = r#"
+            extends = ["base.toml"]
+            remove_words = ["St.", "No."]
+        "#;
+        let rule_set = RuleConfig::from_toml_str(toml_str)
+            .unwrap()
+            .resolve(&dir)
+            .unwrap();
+        let expected = vec![BoundedWords {
+            words: vec!["Mr.".to_string()],
+            before: WordBoundary::NonAlphanumeric,
+            after: WordBoundary::Any,
+        }];
+        assert_eq!(rule_set.bounded_words, expected);
+        let toml_str = rule_set.to_toml_string().unwrap();
+        assert!(
+            toml_str.ends_with(
+                "[[bounded_words]]\nwords = [\"Mr.\"]\nbefore = \"non_alphanumeric\"\nafter = \"any\"\n"
+            ),
+            "{toml_str}"
+        );
+    }
+
+    #[test]
+    fn test_resolve_bounded_delimiters() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("base.toml"),
+            r#"
+            in_delimiters = ["！"]
+            [[bounded_delimiters]]
+            in_delimiters = ["。", "．"]
+            after = "[^」）]"
+            [[bounded_delimiters]]
+            ex_delimiters = ["\n"]
+            before = "[。]"
+            "#,
+        )
+        .unwrap();
+        let toml_str = r#"
+            extends = ["base.toml"]
+            remove_delimiters = ["．", "\n"]
+        "#;
+        let rule_set = RuleConfig::from_toml_str(toml_str)
+            .unwrap()
+            .resolve(&dir)
+            .unwrap();
+        let expected = vec![BoundedDelimiters {
+            in_delimiters: vec!["。".to_string()],
+            ex_delimiters: vec![],
+            before: WordBoundary::Any,
+            after: WordBoundary::Class("[^」）]".to_string()),
+        }];
+        assert_eq!(rule_set.bounded_delimiters, expected);
+        let toml_str = rule_set.to_toml_string().unwrap();
+        assert!(
+            toml_str.ends_with(
+                "[[bounded_delimiters]]\nin_delimiters = [\"。\"]\nbefore = \"any\"\nafter = \"[^」）]\"\n"
+            ),
+            "{toml_str}"
+        );
+    }
+
+    #[test]
+    fn test_resolve_remove_delimiters() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("base.toml"),
+            r#"
+            in_delimiters = ["。", "！"]
+            ex_delimiters = ["\n"]
+            mergeable_delimiters = ["！", "？"]
+            quote_reset_delimiters = ["\n\n", "。"]
+            [in_delimiter_regex]
+            bang = "！+"
+            [ex_delimiter_regex]
+            blank = '\n\s*\n'
+            "#,
+        )
+        .unwrap();
+        let toml_str = r#"
+            extends = ["base.toml"]
+            remove_delimiters = ["！", "\n\n", "！+", '\n\s*\n']
+        "#;
+        let rule_set = RuleConfig::from_toml_str(toml_str)
+            .unwrap()
+            .resolve(&dir)
+            .unwrap();
+        assert_eq!(rule_set.in_delimiters, vec!["。"]);
+        assert_eq!(rule_set.ex_delimiters, vec!["\n"]);
+        assert_eq!(rule_set.mergeable_delimiters, vec!["？"]);
+        assert_eq!(rule_set.quote_reset_delimiters, vec!["。"]);
+        assert!(rule_set.in_delimiter_regex.is_empty());
+        assert!(rule_set.ex_delimiter_regex.is_empty());
+    }
+
+    #[test]
+    fn test_invalid_word_boundary() {
+        let toml_str = r#"
+            [[bounded_words]]
+            words = ["St."]
+            before = "alphanumeric"
+        "#;
+        let msg = RuleConfig::from_toml_str(toml_str)
+            .err()
+            .unwrap()
+            .to_string();
+        assert!(msg.contains("brackets"), "{msg}");
+    }
+
+    #[test]
+    fn test_resolve_template() {
+        let toml_str = r#"
+            extends = ["template:ja"]
+            remove_delimiters = ["！", "!"]
+        "#;
+        let rule_set = RuleConfig::from_toml_str(toml_str)
+            .unwrap()
+            .resolve("")
+            .unwrap();
+        assert_eq!(rule_set.in_delimiters, vec!["。", "．", "？", "?"]);
+        assert_eq!(rule_set.ex_delimiters, vec!["\n", "\r\n", "\r"]);
+        assert_eq!(rule_set.mergeable_delimiters, vec!["。", "．", "？", "?"]);
+        // The defaults of the builder are not written as the rules of the template.
+        assert_eq!(rule_set.max_quote_level, None);
+
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("base.toml"),
+            r#"
+            in_delimiters = ["。"]
+            max_quote_level = 1
+            "#,
+        )
+        .unwrap();
+        let toml_str = r#"extends = ["base.toml", "template:ja"]"#;
+        let rule_set = RuleConfig::from_toml_str(toml_str)
+            .unwrap()
+            .resolve(&dir)
+            .unwrap();
+        assert_eq!(rule_set.max_quote_level, Some(1));
+    }
+
+    #[test]
+    fn test_resolve_unknown_template() {
+        let toml_str = r#"extends = ["template:xx"]"#;
+        let result = RuleConfig::from_toml_str(toml_str).unwrap().resolve("");
+        let msg = result.unwrap_err().to_string();
+        assert!(msg.contains(r#"extends[0] = "template:xx""#), "{msg}");
+    }
+
+    #[test]
+    fn test_resolve_circular() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("a.toml"), r#"extends = ["b.toml"]"#).unwrap();
+        fs::write(dir.path().join("b.toml"), r#"extends = ["a.toml"]"#).unwrap();
+        let toml_str = r#"extends = ["a.toml"]"#;
+        let result = RuleConfig::from_toml_str(toml_str).unwrap().resolve(&dir);
+        let msg = result.unwrap_err().to_string();
+        assert!(msg.contains("circularly"), "{msg}");
+    }
+
+    #[test]
+    fn test_resolve_remove_undefined() {
+        let toml_str = r#"
+            words = ["モーニング娘。"]
+            remove_words = ["モーニング娘。"]
+        "#;
+        let result = RuleConfig::from_toml_str(toml_str).unwrap().resolve("");
+        let msg = result.unwrap_err().to_string();
+        assert!(
+            msg.contains(r#"remove_words[0] = "モーニング娘。""#),
+            "{msg}"
+        );
+    }
 }
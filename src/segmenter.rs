@@ -1,14 +1,76 @@
 //! Segmenter implementation.
+pub mod batch;
 pub mod builder;
-pub use builder::SegmenterBuilder;
+pub mod quote;
+pub mod sentence;
+pub mod stream;
+pub use batch::{SegmentScratch, SegmentedBatch};
+pub use builder::{QuoteMismatchPolicy, QuotePolicy, SegmenterBuilder, SymmetricQuoteRule};
+pub use quote::QuoteSpan;
+pub use sentence::{Delimiter, OwnedSentence, Sentence};
+pub use stream::{IncrementalSegmenter, SentenceReader};
 
+#[cfg(feature = "rayon")]
+mod parallel;
 #[cfg(test)]
 mod tests;
 
-use regex::Regex;
+use std::borrow::Borrow;
+use std::io::BufRead;
+use std::iter::Peekable;
+use std::ops::Range;
+
+use regex::{Captures, Regex};
 
 use crate::bitset::Bitset;
-use crate::matcher::{DelimiterMatcher, QuoteMatcher, WordMatcher};
+use crate::matcher::{
+    DelimiterMatch, DelimiterMatcher, ForceBreakMatcher, QuoteKind, QuoteMatcher, WordMatcher,
+};
+use crate::segmenter::sentence::CharCursor;
+
+/// Range of a sentence with the delimiter that ends it.
+pub(crate) struct Span {
+    pub start: usize,
+    pub end: usize,
+    pub delimiter: Option<DelimiterMatch>,
+    /// Range of the quotation broken by the sentence, if any.
+    pub parent_quote: Option<Range<usize>>,
+}
+
+/// Options to interpret quotation marks.
+pub(crate) struct QuoteOptions {
+    pub max_level: usize,
+    pub max_len: Option<usize>,
+    pub mismatch_policy: QuoteMismatchPolicy,
+    pub symmetric_rule: SymmetricQuoteRule,
+    /// Policy of each quotation pair.
+    pub policies: Vec<QuotePolicy>,
+}
+
+/// Options to trim resulting sentences.
+pub(crate) struct TrimOptions {
+    pub chars: TrimChars,
+    pub drop_empty: bool,
+}
+
+/// Characters trimmed from both ends of sentences.
+pub(crate) enum TrimChars {
+    None,
+    Ascii,
+    Unicode,
+    Class(Regex),
+}
+
+impl TrimChars {
+    fn contains(&self, c: char) -> bool {
+        match self {
+            Self::None => false,
+            Self::Ascii => c.is_ascii_whitespace(),
+            Self::Unicode => c.is_whitespace(),
+            Self::Class(regex) => regex.is_match(c.encode_utf8(&mut [0; 4])),
+        }
+    }
+}
 
 /// Segmenter implementation.
 ///
@@ -22,105 +84,835 @@ pub struct Segmenter {
     quote_matcher: Option<QuoteMatcher>,
     word_matcher: Option<WordMatcher>,
     regex_matchers: Vec<Regex>,
-    max_quote_level: usize,
+    // Forced Breakers
+    force_break_matcher: Option<ForceBreakMatcher>,
+    force_break_regexes: Vec<Regex>,
+    quote_options: QuoteOptions,
+    trim_options: TrimOptions,
 }
 
 impl Segmenter {
-    fn new(
+    #[allow(clippy::too_many_arguments)]
+    const fn new(
         delimiter_matcher: DelimiterMatcher,
         quote_matcher: Option<QuoteMatcher>,
         word_matcher: Option<WordMatcher>,
         regex_matchers: Vec<Regex>,
-        max_quote_level: usize,
+        force_break_matcher: Option<ForceBreakMatcher>,
+        force_break_regexes: Vec<Regex>,
+        quote_options: QuoteOptions,
+        trim_options: TrimOptions,
     ) -> Self {
         Self {
             delimiter_matcher,
             quote_matcher,
             word_matcher,
             regex_matchers,
-            max_quote_level,
+            force_break_matcher,
+            force_break_regexes,
+            quote_options,
+            trim_options,
         }
     }
 
     /// Segments an input text into sentences, returning byte-position ranges.
     pub fn segment<'a>(&'a self, text: &'a str) -> impl Iterator<Item = (usize, usize)> + 'a {
-        let mut no_break = Bitset::new(text.len());
+        self.spans(text).map(|span| (span.start, span.end))
+    }
 
-        // TODO: Parallelization
-        self.find_quotes(text, &mut no_break);
-        self.find_words(text, &mut no_break);
-        self.find_regex(text, &mut no_break);
+    /// Segments an input text into sentences, returning [`Sentence`]s
+    /// that provide more information than [`Self::segment`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use easy_segmenter::SegmenterBuilder;
+    ///
+    /// let seg = SegmenterBuilder::new()
+    ///     .in_delimiters(["。"])
+    ///     .ex_delimiters(["\n"])
+    ///     .build()
+    ///     .unwrap();
+    /// let text = "なるほど\nその通りですね。";
+    /// let sentences: Vec<_> = seg.sentences(text).collect();
+    ///
+    /// assert_eq!(sentences[0].as_str(), "なるほど");
+    /// assert_eq!(sentences[0].byte_range(), 0..12);
+    /// assert_eq!(sentences[0].char_range(), 0..4);
+    /// assert_eq!(sentences[0].delimiter().unwrap().as_str(), "\n");
+    /// assert!(!sentences[0].delimiter().unwrap().is_inclusive());
+    ///
+    /// assert_eq!(sentences[1].as_str(), "その通りですね。");
+    /// assert_eq!(sentences[1].char_range(), 5..13);
+    /// assert_eq!(sentences[1].delimiter().unwrap().as_str(), "。");
+    /// assert!(sentences[1].delimiter().unwrap().is_inclusive());
+    /// assert_eq!(sentences[1].index(), 1);
+    /// ```
+    pub fn sentences<'a>(&'a self, text: &'a str) -> impl Iterator<Item = Sentence<'a>> + 'a {
+        let mut cursor = CharCursor::default();
+        self.spans(text).enumerate().map(move |(index, span)| {
+            let char_start = cursor.advance(text, span.start);
+            let char_end = cursor.advance(text, span.end);
+            Sentence::new(text, span, char_start..char_end, index)
+        })
+    }
 
-        let mut start_pos = 0;
+    /// Segments a text read from `reader` into sentences incrementally,
+    /// returning [`OwnedSentence`]s whose positions are offsets from the beginning of the stream.
+    ///
+    /// The results are the same as [`Self::sentences`] for the entire text.
+    /// To this end, the text after the last determined sentence is held back
+    /// until it is determined by subsequent text, i.e., until
+    ///  - all quotations in the text are closed,
+    ///  - the longest delimiter, quotation mark, and word can be matched, and
+    ///  - no regex match starting in the text can be extended.
+    ///
+    /// The memory usage is therefore bounded by the longest sentence in most cases,
+    /// and each part of the stream is scanned a constant number of times.
+    /// The results are the same as [`Self::segment`] unless a quotation is longer than
+    /// [`SentenceReader::max_held_quote_len`], which is 1 MiB by default;
+    /// such a quotation is regarded as unclosed, so that a missing closing mark
+    /// does not hold back the rest of the stream.
+    /// Use [`SegmenterBuilder::max_quote_len`] to limit quotations in both results.
+    /// Regex patterns are analyzed through [`Regex::as_str`], so options given by
+    /// [`regex::RegexBuilder`] should be written as inline flags such as `(?i)`.
+    /// If a regex pattern requires look-around assertions not supported by the analysis,
+    /// e.g., Unicode word boundaries on non-ASCII text, the rest of the stream is held back.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::BufReader;
+    ///
+    /// use easy_segmenter::SegmenterBuilder;
+    ///
+    /// let seg = SegmenterBuilder::new()
+    ///     .in_delimiters(["。"])
+    ///     .ex_delimiters(["\n"])
+    ///     .quotes([('「', '」')])
+    ///     .build()
+    ///     .unwrap();
+    /// let text = "私は「はい。そうです。」と答えた。\nなるほど";
+    /// let reader = BufReader::with_capacity(4, text.as_bytes());
+    /// let sentences: Vec<_> = seg
+    ///     .segment_reader(reader)
+    ///     .map(|s| s.unwrap().into_string())
+    ///     .collect();
+    /// let expected = vec!["私は「はい。そうです。」と答えた。", "なるほど"];
+    /// assert_eq!(sentences, expected);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// The iterator returns an error if reading fails or the stream is not valid UTF-8.
+    pub fn segment_reader<R>(&self, reader: R) -> SentenceReader<'_, R>
+    where
+        R: BufRead,
+    {
+        SentenceReader::new(self, reader)
+    }
 
-        self.delimiter_matcher.iter(text).filter_map(move |m| {
-            // Handling the last imaginary terminator.
-            if m.start == m.end {
-                if start_pos < text.len() {
-                    // The case that the last character does not have any delimiter.
-                    return Some((start_pos, text.len()));
-                } else {
-                    return None;
-                }
+    fn spans<'a>(&'a self, text: &'a str) -> impl Iterator<Item = Span> + 'a {
+        let mut detection = Detection::new(0..text.len(), false);
+        self.detect(text, 0..text.len(), &mut detection);
+        self.spans_with(text, 0..text.len(), detection)
+            .filter_map(|span| self.trim(text, span))
+    }
+
+    /// Trims a sentence according to the trim options,
+    /// or returns `None` if the sentence should be dropped.
+    ///
+    /// Sentences are trimmed only for output since the untrimmed ends are used to determine
+    /// the positions where texts can be cut in streaming and parallel segmentation.
+    pub(crate) fn trim(&self, text: &str, mut span: Span) -> Option<Span> {
+        let chars = &self.trim_options.chars;
+        if !matches!(chars, TrimChars::None) {
+            let sentence = &text[span.start..span.end];
+            let trimmed = sentence.trim_start_matches(|c| chars.contains(c));
+            span.start = span.end - trimmed.len();
+            span.end = span.start + trimmed.trim_end_matches(|c| chars.contains(c)).len();
+            // Inclusive delimiters are clipped to stay in the trimmed sentence.
+            if let Some(m) = span.delimiter.as_mut().filter(|m| m.is_in_delimiter) {
+                m.start = m.start.clamp(span.start, span.end);
+                m.end = m.end.clamp(span.start, span.end);
             }
-            // if is_in_delimiter, the delimiter should be inclusive in the segment;
-            // otherwise, the delimiter should be exclusive in the segment.
-            let end_pos = if m.is_in_delimiter { m.end } else { m.start };
-            if end_pos != 0 && no_break.get(end_pos - 1) {
-                None
-            } else if start_pos == end_pos {
-                start_pos = m.end;
-                None
-            } else {
-                let range = (start_pos, end_pos);
-                start_pos = m.end;
-                Some(range)
+        }
+        if self.trim_options.drop_empty && span.start == span.end {
+            return None;
+        }
+        Some(span)
+    }
+
+    /// Detects positions that should and should not be broken in `text[range]`,
+    /// where the other parts of `text` are used only as the context of regex patterns.
+    pub(crate) fn detect(&self, text: &str, range: Range<usize>, detection: &mut Detection) {
+        self.find_quotes(text, range.clone(), detection);
+        self.find_words(text, range.clone(), detection);
+        self.find_regex(text, range.clone(), detection);
+        self.find_forced_breaks(text, range, detection);
+    }
+
+    /// Segments `text[range]` using positions detected by [`Self::detect`],
+    /// where the other parts of `text` are used only by regex delimiters.
+    pub(crate) fn spans_with<'a, D>(
+        &'a self,
+        text: &'a str,
+        range: Range<usize>,
+        detection: D,
+    ) -> impl Iterator<Item = Span> + 'a
+    where
+        D: Borrow<Detection> + 'a,
+    {
+        self.spans_from(text, range.clone(), range.start, detection)
+    }
+
+    /// Segments `text[range]` in the same manner as [`Self::spans_with`],
+    /// where the first sentence starts at `start` instead of `range.start`.
+    ///
+    /// This resumes segmentation from a position returned by [`Spans::resumable_range`].
+    pub(crate) fn spans_from<'a, D>(
+        &'a self,
+        text: &'a str,
+        range: Range<usize>,
+        start: usize,
+        detection: D,
+    ) -> Spans<'a, D, impl Iterator<Item = DelimiterMatch> + 'a>
+    where
+        D: Borrow<Detection> + 'a,
+    {
+        Spans {
+            segmenter: self,
+            text,
+            end: range.end,
+            delimiter_end: range.start,
+            delimiters: self.delimiter_matcher.iter(text, range).peekable(),
+            detection,
+            start_pos: start,
+            quote_cursor: 0,
+            forced_cursor: 0,
+        }
+    }
+
+    /// Returns the maximum length of patterns that can be matched at a position.
+    pub(crate) fn max_pattern_len(&self) -> usize {
+        let quote_len = self.quote_matcher.as_ref().map_or(0, |m| m.max_len());
+        let word_len = self.word_matcher.as_ref().map_or(0, |m| m.max_len());
+        let force_break_len = self.force_break_matcher.as_ref().map_or(0, |m| m.max_len());
+        self.delimiter_matcher
+            .max_len()
+            .max(quote_len)
+            .max(word_len)
+            .max(force_break_len)
+    }
+
+    /// Returns all the regex patterns, including those for delimiters and forced breaks.
+    pub(crate) fn regex_matchers(&self) -> impl Iterator<Item = &Regex> {
+        self.regex_matchers
+            .iter()
+            .chain(&self.force_break_regexes)
+            .chain(self.delimiter_matcher.regexes())
+    }
+
+    fn find_quotes(&self, text: &str, range: Range<usize>, detection: &mut Detection) {
+        if self.quote_matcher.is_some() {
+            let mut stack = std::mem::take(&mut detection.quote_stack);
+            stack.clear();
+            self.scan_quotes(text, range.clone(), range.end, &mut stack, detection);
+            detection.split_quotes.sort_unstable_by_key(|r| r.start);
+            // Unclosed quotations can be closed in the subsequent text,
+            // so the end of the text is also crossed until they expire.
+            for q in &mut stack {
+                let end = self
+                    .quote_options
+                    .max_len
+                    .map_or(usize::MAX, |max_len| q.expiry(&text[..range.end], max_len));
+                detection.cross(q.start..end.min(range.end + 1));
+                detection.record_quote(q.start..q.mark_end, q.id, false);
             }
-        })
+            detection.quote_stack = stack;
+        }
     }
 
-    fn find_quotes(&self, text: &str, detected: &mut Bitset) {
-        if let Some(quote_matcher) = self.quote_matcher.as_ref() {
-            let mut stack = vec![];
-            for m in quote_matcher.iter(text) {
-                if m.is_open {
-                    stack.push((m.start, m.id));
-                    continue;
+    /// Processes quotation marks in `text[range]` that end by `until`,
+    /// updating the open quotations in `stack` and sending the detected positions to `sink`.
+    ///
+    /// Returns the position from which the scan can be resumed when `text` is extended,
+    /// i.e., the start of the first mark not processed, or
+    /// the earliest position from which a new mark can be completed.
+    fn scan_quotes<S>(
+        &self,
+        text: &str,
+        range: Range<usize>,
+        until: usize,
+        stack: &mut Vec<OpenQuote>,
+        sink: &mut S,
+    ) -> usize
+    where
+        S: QuoteSink,
+    {
+        let Some(quote_matcher) = self.quote_matcher.as_ref() else {
+            return range.end;
+        };
+        let options = &self.quote_options;
+        let mut resume_pos = range.start;
+        for m in quote_matcher.iter(&text[..range.end], range.start) {
+            if m.end > until {
+                return m.start;
+            }
+            resume_pos = m.end;
+            // Discards quotations exceeding the maximum length,
+            // which are at the bottom of the stack.
+            if let Some(max_len) = options.max_len {
+                let mut num_expired = 0;
+                for q in stack.iter_mut() {
+                    if !q.is_expired(&text[..range.end], max_len, m.end) {
+                        break;
+                    }
+                    num_expired += 1;
                 }
-                if stack.is_empty() {
-                    continue;
+                for q in stack.drain(..num_expired) {
+                    sink.cross(q.start..q.expiry.unwrap());
+                    sink.record_quote(q.start..q.mark_end, q.id, false);
+                }
+            }
+            let is_open = match m.kind {
+                QuoteKind::Open => true,
+                QuoteKind::Close => false,
+                QuoteKind::Symmetric => {
+                    let is_opened = stack.iter().any(|q| q.id == m.id);
+                    let (prev, next) = (
+                        text[..m.start].chars().next_back(),
+                        text[m.end..].chars().next(),
+                    );
+                    if is_opened && options.symmetric_rule.can_close(prev, next) {
+                        false
+                    } else if !is_opened && options.symmetric_rule.can_open(prev, next) {
+                        true
+                    } else {
+                        continue;
+                    }
                 }
-                let (start, id) = stack.last().cloned().unwrap();
-                if id != m.id {
-                    continue; // No correspondence.
+                QuoteKind::Reset => {
+                    discard_quotes(stack, 0, m.end, sink);
+                    continue;
                 }
-                // NOTE: Since nested quates are processed, this algorithm runs in
-                // O(nk) time, where n is text.len() and k is the max nesting level.
-                if stack.len() <= self.max_quote_level {
-                    detected.set_range(start..m.end);
+            };
+            if is_open {
+                stack.push(OpenQuote::new(m.start..m.end, m.id));
+                continue;
+            }
+            let is_matched = match stack.last() {
+                None => false,
+                Some(q) if q.id == m.id => true,
+                // No correspondence.
+                Some(_) => match options.mismatch_policy {
+                    QuoteMismatchPolicy::Skip => false,
+                    QuoteMismatchPolicy::PopToMatch => {
+                        let i = stack.iter().rposition(|q| q.id == m.id);
+                        if let Some(i) = i {
+                            discard_quotes(stack, i + 1, m.end, sink);
+                        }
+                        i.is_some()
+                    }
+                    QuoteMismatchPolicy::Reset => {
+                        discard_quotes(stack, 0, m.end, sink);
+                        false
+                    }
+                },
+            };
+            if !is_matched {
+                sink.record_quote(m.start..m.end, m.id, false);
+                continue;
+            }
+            // NOTE: Since nested quates are processed, this algorithm runs in
+            // O(nk) time, where n is text.len() and k is the max nesting level.
+            let start = stack.last().unwrap().start;
+            if stack.len() <= options.max_level {
+                if self.is_split_quote(&text[start..m.end], m.id) {
+                    // Attaches the closing mark to the last sentence in the quotation.
+                    let last_start = text[..m.start].char_indices().next_back().unwrap().0;
+                    sink.protect(last_start..m.start);
+                    sink.split_quote(start..m.end);
+                } else {
+                    sink.protect(start..m.end);
                 }
-                stack.pop();
             }
+            sink.cross(start..m.end);
+            sink.record_quote(start..m.end, m.id, true);
+            stack.pop();
+        }
+        // A mark completed by subsequent text starts within the maximum length from the end.
+        let mut pos = range.end.saturating_sub(quote_matcher.max_len());
+        while !text.is_char_boundary(pos) {
+            pos -= 1;
+        }
+        resume_pos.max(pos)
+    }
+
+    /// Checks if sentences inside a quotation should be broken.
+    fn is_split_quote(&self, quote: &str, id: usize) -> bool {
+        match self.quote_options.policies[id] {
+            QuotePolicy::Protect => false,
+            QuotePolicy::SplitInside => true,
+            QuotePolicy::SplitIfLongerThan(len) => quote.chars().nth(len).is_some(),
         }
     }
 
-    fn find_words(&self, text: &str, detected: &mut Bitset) {
+    fn find_words(&self, text: &str, range: Range<usize>, detection: &mut Detection) {
         if let Some(word_matcher) = self.word_matcher.as_ref() {
-            for m in word_matcher.iter(text) {
-                detected.set_range(m.start..m.end);
+            for m in word_matcher.iter(text, range) {
+                if m.parts.is_empty() {
+                    detection.protect(m.start..m.end);
+                }
+                for part in m.parts {
+                    detection.protect(part);
+                }
+                // The whole word is crossed since the protection depends on all the characters.
+                detection.cross(m.start..m.end);
             }
         }
     }
 
-    fn find_regex(&self, text: &str, detected: &mut Bitset) {
+    fn find_regex(&self, text: &str, range: Range<usize>, detection: &mut Detection) {
         for re in &self.regex_matchers {
-            for cap in re.captures_iter(text) {
+            for cap in captures_from(re, text, range.start)
+                .take_while(|cap| cap.get(0).unwrap().start() < range.end)
+            {
                 for idx in 1..cap.len() {
                     if let Some(m) = cap.get(idx) {
-                        detected.set_range(m.range());
+                        detection.protect(m.range());
+                    }
+                }
+                detection.cross(cap.get(0).unwrap().range());
+            }
+        }
+    }
+
+    fn find_forced_breaks(&self, text: &str, range: Range<usize>, detection: &mut Detection) {
+        if let Some(force_break_matcher) = self.force_break_matcher.as_ref() {
+            for m in force_break_matcher.iter(text, range.clone()) {
+                detection.forced_breaks.push(m.end);
+                detection.cross(m);
+            }
+        }
+        for re in &self.force_break_regexes {
+            for cap in captures_from(re, text, range.start)
+                .take_while(|cap| cap.get(0).unwrap().start() < range.end)
+            {
+                let whole = cap.get(0).unwrap();
+                if cap.len() == 1 {
+                    detection.forced_breaks.push(whole.end());
+                }
+                for idx in 1..cap.len() {
+                    if let Some(m) = cap.get(idx) {
+                        detection.forced_breaks.push(m.start());
+                    }
+                }
+                detection.cross(whole.range());
+            }
+        }
+        detection.forced_breaks.sort_unstable();
+        detection.forced_breaks.dedup();
+    }
+}
+
+/// Iterator over sentences created by [`Segmenter::spans_from`].
+pub(crate) struct Spans<'a, D, I>
+where
+    I: Iterator<Item = DelimiterMatch>,
+{
+    segmenter: &'a Segmenter,
+    text: &'a str,
+    end: usize,
+    detection: D,
+    delimiters: Peekable<I>,
+    // End of the last delimiter taken from `delimiters`.
+    delimiter_end: usize,
+    // Start of the next sentence.
+    start_pos: usize,
+    quote_cursor: usize,
+    forced_cursor: usize,
+}
+
+impl<D, I> Spans<'_, D, I>
+where
+    D: Borrow<Detection>,
+    I: Iterator<Item = DelimiterMatch>,
+{
+    /// Processes the next delimiter or forced break, returning `Some(None)` if it does not
+    /// produce a sentence and `None` if the text is exhausted.
+    pub fn step(&mut self) -> Option<Option<Span>> {
+        let detection = self.detection.borrow();
+        let m = *self.delimiters.peek()?;
+        // Forced breaks are applied before the delimiters starting at or after them,
+        // regardless of the protected positions.
+        if let Some(&pos) = detection.forced_breaks.get(self.forced_cursor) {
+            if pos <= m.start {
+                self.forced_cursor += 1;
+                if self.start_pos < pos {
+                    let start = self.start_pos;
+                    self.start_pos = pos;
+                    return Some(Some(Span {
+                        start,
+                        end: pos,
+                        delimiter: None,
+                        parent_quote: detection.parent_quote(start..pos, &mut self.quote_cursor),
+                    }));
+                }
+                return Some(None);
+            }
+        }
+        self.delimiters.next();
+        self.delimiter_end = m.end;
+        // Delimiters overlapping trailing attachers of the previous delimiter are skipped.
+        if m.start < self.start_pos {
+            return Some(None);
+        }
+        // Handling the last imaginary terminator.
+        if m.start == m.end {
+            if self.start_pos < self.end {
+                // The case that the last character does not have any delimiter.
+                let start = self.start_pos;
+                self.start_pos = self.end;
+                return Some(Some(Span {
+                    start,
+                    end: self.end,
+                    delimiter: None,
+                    parent_quote: detection.parent_quote(start..self.end, &mut self.quote_cursor),
+                }));
+            }
+            return None;
+        }
+        // if is_in_delimiter, the delimiter should be inclusive in the segment;
+        // otherwise, the delimiter should be exclusive in the segment.
+        let mut end_pos = if m.is_in_delimiter { m.end } else { m.start };
+        if end_pos != 0 && detection.is_protected(end_pos - 1) {
+            return Some(None);
+        }
+        let mut m = m;
+        if m.is_in_delimiter {
+            let delimiter_matcher = &self.segmenter.delimiter_matcher;
+            // Delimiters and trailing attachers are not merged across forced breaks.
+            let forced = &detection.forced_breaks[self.forced_cursor..];
+            let limit = forced
+                .get(forced.partition_point(|&pos| pos < m.end))
+                .map_or(self.text.len(), |&pos| pos);
+            if delimiter_matcher.is_mergeable(self.text, &m) {
+                while let Some(&next) = self.delimiters.peek() {
+                    if next.start != m.end
+                        || next.end > limit
+                        || !delimiter_matcher.is_mergeable(self.text, &next)
+                        || detection.is_protected(next.end - 1)
+                    {
+                        break;
                     }
+                    m.end = next.end;
+                    self.delimiters.next();
+                    self.delimiter_end = next.end;
                 }
             }
+            m.end = delimiter_matcher.attach_trailing(self.text, m.end, limit);
+            end_pos = m.end;
+        }
+        if self.start_pos == end_pos {
+            self.start_pos = m.end;
+            return Some(None);
+        }
+        let start = self.start_pos;
+        self.start_pos = m.end;
+        Some(Some(Span {
+            start,
+            end: end_pos,
+            delimiter: Some(m),
+            parent_quote: detection.parent_quote(start..end_pos, &mut self.quote_cursor),
+        }))
+    }
+
+    /// Returns the start of the next sentence.
+    pub const fn start_pos(&self) -> usize {
+        self.start_pos
+    }
+
+    /// Returns the position of the next delimiter or forced break to be processed,
+    /// or `usize::MAX` if the text is exhausted.
+    pub fn next_pos(&mut self) -> usize {
+        let detection = self.detection.borrow();
+        let next_forced = detection
+            .forced_breaks
+            .get(self.forced_cursor)
+            .map_or(usize::MAX, |&pos| pos);
+        self.delimiters
+            .peek()
+            .map_or(usize::MAX, |m| m.start)
+            .min(next_forced)
+    }
+
+    /// Returns the range of positions `pos` from which the rest of the sentences are produced
+    /// by [`Segmenter::spans_from`] with `pos..` as the range and [`Self::start_pos`] as the start,
+    /// provided that the detection is also resumed at `pos` without changing the results.
+    pub fn resumable_range(&mut self) -> Range<usize> {
+        let detection = self.detection.borrow();
+        // Forced breaks found before `pos` are lost unless they are no-ops.
+        let next_forced = detection.forced_breaks[self.forced_cursor..]
+            .iter()
+            .find(|&&pos| pos > self.start_pos)
+            .map_or(usize::MAX, |&pos| pos);
+        let next_delimiter = self.delimiters.peek().map_or(self.end, |m| m.start);
+        // Regex delimiters are searched again from `pos` if their whole matches start before it.
+        let next_regex = self
+            .segmenter
+            .delimiter_matcher
+            .regexes()
+            .filter_map(|re| re.find_at(self.text, self.delimiter_end))
+            .map(|m| m.start())
+            .min()
+            .unwrap_or(usize::MAX);
+        let start = self.delimiter_end.max(self.start_pos);
+        let end = (next_delimiter + 1)
+            .min(next_forced)
+            .min(next_regex.saturating_add(1));
+        start..end.max(start)
+    }
+}
+
+impl<D, I> Iterator for Spans<'_, D, I>
+where
+    D: Borrow<Detection>,
+    I: Iterator<Item = DelimiterMatch>,
+{
+    type Item = Span;
+
+    fn next(&mut self) -> Option<Span> {
+        loop {
+            if let Some(span) = self.step()? {
+                return Some(span);
+            }
+        }
+    }
+}
+
+/// Positions detected by the rules for not segmenting sentences,
+/// which are stored for a range of the text.
+#[derive(Default)]
+pub(crate) struct Detection {
+    offset: usize,
+    len: usize,
+    /// Positions that should not be broken.
+    no_break: Bitset,
+    /// Positions inside detected patterns, which are tracked only if needed.
+    /// A text cannot be cut at these positions without changing the results.
+    crossed: Option<Bitset>,
+    /// Ranges of quotations whose inside can be broken, sorted by the start positions.
+    split_quotes: Vec<Range<usize>>,
+    /// Positions that should be broken regardless of `no_break`, sorted in ascending order.
+    forced_breaks: Vec<usize>,
+    /// Detected quotations and unmatched marks, which are recorded only if needed.
+    quote_spans: Option<Vec<QuoteSpan>>,
+    // Buffer of the quotation stack, which is reused across detections.
+    quote_stack: Vec<OpenQuote>,
+}
+
+/// Quotation that is opened but not closed yet.
+struct OpenQuote {
+    start: usize,
+    // End of the opening mark.
+    mark_end: usize,
+    id: usize,
+    // Position from which the quotation exceeds the maximum length, which is computed lazily.
+    expiry: Option<usize>,
+}
+
+impl OpenQuote {
+    const fn new(mark: Range<usize>, id: usize) -> Self {
+        Self {
+            start: mark.start,
+            mark_end: mark.end,
+            id,
+            expiry: None,
+        }
+    }
+
+    /// Checks if the quotation has more than `max_len` characters until `pos`.
+    fn is_expired(&mut self, text: &str, max_len: usize, pos: usize) -> bool {
+        // The number of characters never exceeds the number of bytes.
+        pos - self.start > max_len && self.expiry(text, max_len) <= pos
+    }
+
+    /// Returns the minimum end position at which the quotation has more than `max_len` characters,
+    /// or `usize::MAX` if it is not found in `text`.
+    fn expiry(&mut self, text: &str, max_len: usize) -> usize {
+        *self.expiry.get_or_insert_with(|| {
+            text[self.start..]
+                .char_indices()
+                .nth(max_len)
+                .map_or(usize::MAX, |(i, c)| self.start + i + c.len_utf8())
+        })
+    }
+}
+
+/// Discards open quotations in `stack[from..]` at `pos`.
+///
+/// The positions from the start of the discarded quotations are crossed
+/// since the text cannot be cut there without changing the stack.
+fn discard_quotes<S>(stack: &mut Vec<OpenQuote>, from: usize, pos: usize, sink: &mut S)
+where
+    S: QuoteSink,
+{
+    if let Some(q) = stack.get(from) {
+        sink.cross(q.start..pos);
+    }
+    for q in stack.drain(from..) {
+        sink.record_quote(q.start..q.mark_end, q.id, false);
+    }
+}
+
+/// Receiver of the positions detected by quotations.
+trait QuoteSink {
+    /// Marks positions in `r` as not to be broken.
+    fn protect(&mut self, r: Range<usize>);
+    /// Marks positions strictly inside `r` as crossed.
+    fn cross(&mut self, r: Range<usize>);
+    /// Adds a quotation whose inside can be broken.
+    fn split_quote(&mut self, r: Range<usize>);
+    /// Records a quotation or an unmatched mark if needed.
+    fn record_quote(&mut self, r: Range<usize>, pair_id: usize, is_matched: bool);
+}
+
+impl QuoteSink for Detection {
+    fn protect(&mut self, r: Range<usize>) {
+        Self::protect(self, r);
+    }
+
+    fn cross(&mut self, r: Range<usize>) {
+        Self::cross(self, r);
+    }
+
+    fn split_quote(&mut self, r: Range<usize>) {
+        self.split_quotes.push(r);
+    }
+
+    fn record_quote(&mut self, r: Range<usize>, pair_id: usize, is_matched: bool) {
+        Self::record_quote(self, r, pair_id, is_matched);
+    }
+}
+
+impl Detection {
+    pub fn new(range: Range<usize>, track_crossed: bool) -> Self {
+        let mut detection = Self::default();
+        detection.reset(range, track_crossed);
+        detection
+    }
+
+    /// Clears the detected positions and sets a new range, reusing the allocated memory.
+    pub fn reset(&mut self, range: Range<usize>, track_crossed: bool) {
+        self.offset = range.start;
+        self.len = range.len();
+        self.no_break.reset(self.len);
+        self.split_quotes.clear();
+        self.forced_breaks.clear();
+        if !track_crossed {
+            self.crossed = None;
+        } else if let Some(crossed) = self.crossed.as_mut() {
+            crossed.reset(self.len + 1);
+        } else {
+            self.crossed = Some(Bitset::new(self.len + 1));
+        }
+    }
+
+    /// Marks positions in `r` as not to be broken.
+    /// Positions out of the range are ignored.
+    fn protect(&mut self, r: Range<usize>) {
+        let start = r.start.max(self.offset);
+        let end = r.end.min(self.offset + self.len);
+        if start < end {
+            self.no_break
+                .set_range(start - self.offset..end - self.offset);
+        }
+    }
+
+    /// Marks positions strictly inside `r` as crossed.
+    /// Positions out of the range are ignored.
+    fn cross(&mut self, r: Range<usize>) {
+        if let Some(crossed) = self.crossed.as_mut() {
+            let start = (r.start + 1).max(self.offset);
+            let end = r.end.min(self.offset + self.len + 1);
+            if start < end {
+                crossed.set_range(start - self.offset..end - self.offset);
+            }
+        }
+    }
+
+    /// Checks if `pos` should not be broken, where positions out of the range are not.
+    pub fn is_protected(&self, pos: usize) -> bool {
+        pos >= self.offset && self.no_break.get(pos - self.offset)
+    }
+
+    /// Records a quotation or an unmatched mark if needed.
+    fn record_quote(&mut self, range: Range<usize>, pair_id: usize, is_matched: bool) {
+        if let Some(quote_spans) = self.quote_spans.as_mut() {
+            quote_spans.push(QuoteSpan::new(range, pair_id, is_matched));
         }
     }
+
+    /// Returns the outermost split quotation broken by a sentence in `range`,
+    /// i.e., overlapping but not contained in the sentence.
+    ///
+    /// `cursor` is the index of the first quotation that can be returned,
+    /// which is advanced as sentences are given in order.
+    fn parent_quote(&self, range: Range<usize>, cursor: &mut usize) -> Option<Range<usize>> {
+        while self
+            .split_quotes
+            .get(*cursor)
+            .is_some_and(|q| q.end <= range.start)
+        {
+            *cursor += 1;
+        }
+        self.split_quotes[*cursor..]
+            .iter()
+            .take_while(|q| q.start < range.end)
+            .filter(|q| range.start < q.end)
+            .find(|q| q.start < range.start || range.end < q.end)
+            .cloned()
+    }
+
+    /// Checks if the text can be cut at the end of a sentence `pos`
+    /// without changing the results. Crossed positions must be tracked.
+    pub fn is_cuttable(&self, pos: usize) -> bool {
+        // The previous position affects how the next sentence starts.
+        !self.is_crossed(pos) && !self.is_protected(pos - 1)
+    }
+
+    /// Checks if a detected pattern is matched across `pos`. Crossed positions must be tracked.
+    pub fn is_crossed(&self, pos: usize) -> bool {
+        self.crossed.as_ref().unwrap().get(pos - self.offset)
+    }
+}
+
+/// Iterates captures in `text[from..]` in the same manner as [`Regex::captures_iter`],
+/// where `text[..from]` is used as the context of look-around assertions.
+fn captures_from<'a, 't>(
+    re: &'a Regex,
+    text: &'t str,
+    from: usize,
+) -> impl Iterator<Item = Captures<'t>> + 'a
+where
+    't: 'a,
+{
+    let mut pos = from;
+    let mut last_end = None;
+    std::iter::from_fn(move || loop {
+        if pos > text.len() {
+            return None;
+        }
+        let cap = re.captures_at(text, pos)?;
+        let m = cap.get(0).unwrap();
+        if m.is_empty() && Some(m.end()) == last_end {
+            // Skips an empty match overlapping with the end of the previous match.
+            pos += 1;
+            continue;
+        }
+        pos = m.end();
+        last_end = Some(m.end());
+        return Some(cap);
+    })
 }
@@ -0,0 +1,139 @@
+//! Segmentation of many documents with reusable buffers.
+use crate::segmenter::{Detection, Segmenter};
+
+/// Buffers reused across calls of [`Segmenter::segment_with`]
+/// to avoid allocating working memory for each text.
+#[derive(Default)]
+pub struct SegmentScratch {
+    detection: Detection,
+}
+
+impl SegmentScratch {
+    /// Creates empty buffers, which grow to fit the longest text segmented.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Sentence ranges of multiple documents stored in flat arrays,
+/// which is returned by [`Segmenter::segment_batch`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SegmentedBatch {
+    ranges: Vec<(usize, usize)>,
+    // The ranges of the i-th document are ranges[offsets[i]..offsets[i + 1]].
+    offsets: Vec<usize>,
+}
+
+impl SegmentedBatch {
+    fn new() -> Self {
+        Self {
+            ranges: vec![],
+            offsets: vec![0],
+        }
+    }
+
+    /// Returns the number of documents.
+    pub const fn len(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    /// Checks if the batch has no document.
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the byte-position ranges of sentences in the `i`-th document.
+    pub fn get(&self, i: usize) -> Option<&[(usize, usize)]> {
+        let (&start, &end) = (self.offsets.get(i)?, self.offsets.get(i + 1)?);
+        Some(&self.ranges[start..end])
+    }
+
+    /// Iterates the byte-position ranges of sentences in each document.
+    pub fn iter(&self) -> impl Iterator<Item = &[(usize, usize)]> + '_ {
+        self.offsets.windows(2).map(|w| &self.ranges[w[0]..w[1]])
+    }
+
+    /// Returns the byte-position ranges of sentences in all the documents.
+    pub fn ranges(&self) -> &[(usize, usize)] {
+        &self.ranges
+    }
+
+    /// Returns the offsets of each document in [`Self::ranges`],
+    /// whose length is the number of documents plus one.
+    pub fn offsets(&self) -> &[usize] {
+        &self.offsets
+    }
+}
+
+impl Segmenter {
+    /// Segments an input text into sentences in the same manner as [`Self::segment`],
+    /// using `scratch` as the working memory.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use easy_segmenter::segmenter::SegmentScratch;
+    /// use easy_segmenter::SegmenterBuilder;
+    ///
+    /// let seg = SegmenterBuilder::new()
+    ///     .in_delimiters(["。"])
+    ///     .build()
+    ///     .unwrap();
+    /// let mut scratch = SegmentScratch::new();
+    /// for text in ["はい。そうです。", "いいえ。"] {
+    ///     let expected: Vec<_> = seg.segment(text).collect();
+    ///     let ranges: Vec<_> = seg.segment_with(text, &mut scratch).collect();
+    ///     assert_eq!(ranges, expected);
+    /// }
+    /// ```
+    pub fn segment_with<'a>(
+        &'a self,
+        text: &'a str,
+        scratch: &'a mut SegmentScratch,
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        let detection = &mut scratch.detection;
+        detection.reset(0..text.len(), false);
+        self.detect(text, 0..text.len(), detection);
+        self.spans_with(text, 0..text.len(), &*detection)
+            .filter_map(|span| self.trim(text, span))
+            .map(|span| (span.start, span.end))
+    }
+
+    /// Segments multiple documents into sentences, returning byte-position ranges
+    /// in a flat structure.
+    ///
+    /// The working memory is allocated once and reused for all the documents,
+    /// which is faster than calling [`Self::segment`] for many short documents.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use easy_segmenter::SegmenterBuilder;
+    ///
+    /// let seg = SegmenterBuilder::new()
+    ///     .in_delimiters(["。"])
+    ///     .build()
+    ///     .unwrap();
+    /// let batch = seg.segment_batch(&["はい。そうです。", "", "いいえ。"]);
+    /// assert_eq!(batch.len(), 3);
+    /// assert_eq!(batch.get(0), Some(&[(0, 9), (9, 24)][..]));
+    /// assert_eq!(batch.get(1), Some(&[][..]));
+    /// assert_eq!(batch.get(2), Some(&[(0, 12)][..]));
+    /// assert_eq!(batch.offsets(), &[0, 2, 2, 3]);
+    /// ```
+    pub fn segment_batch<S>(&self, texts: &[S]) -> SegmentedBatch
+    where
+        S: AsRef<str>,
+    {
+        let mut scratch = SegmentScratch::new();
+        let mut batch = SegmentedBatch::new();
+        batch.offsets.reserve(texts.len());
+        for text in texts {
+            batch
+                .ranges
+                .extend(self.segment_with(text.as_ref(), &mut scratch));
+            batch.offsets.push(batch.ranges.len());
+        }
+        batch
+    }
+}
@@ -1,13 +1,325 @@
 //! Builder of [`Segmenter`] to define segmentation rules.
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
 use regex::Regex;
+use serde::de::{self, Deserializer, MapAccess, Visitor};
+use serde::ser::{SerializeMap, Serializer};
+use serde_derive::{Deserialize, Serialize};
 
 use crate::errors::{EasySegmenterError, Result};
-use crate::matcher::{DelimiterMatcher, QuoteMatcher, WordMatcher};
-use crate::segmenter::Segmenter;
+use crate::matcher::{self, DelimiterMatcher, ForceBreakMatcher, QuoteMatcher, WordMatcher};
+use crate::rule::{rule_error, BoundedDelimiters, BoundedWords, RuleConfig};
+use crate::segmenter::{QuoteOptions, Segmenter, TrimChars, TrimOptions};
 
 /// The default value of the maximum nested level of quotations.
 pub const DEFAULT_MAX_QUOTE_LEVEL: usize = 3;
 
+/// Rule to determine whether a symmetric quotation mark such as `"` opens or closes a quotation.
+///
+/// A symmetric mark is regarded as closing if a quotation with the same mark is open,
+/// and as opening otherwise. Each rule restricts the positions of opening and closing marks
+/// by the adjacent characters, where marks violating the rule are ignored.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SymmetricQuoteRule {
+    /// Marks open and close quotations alternately regardless of the adjacent characters.
+    #[default]
+    Toggle,
+    /// A mark opens a quotation only if it is not preceded by an ASCII alphanumeric character
+    /// and is followed by a non-whitespace character, e.g., at the beginning of a line or
+    /// after whitespace, and closes a quotation only if it is preceded by a non-whitespace
+    /// character and is not followed by an ASCII alphanumeric character.
+    ///
+    /// This rule is suitable for English text with apostrophes such as `don't`,
+    /// and also works for text without spaces between words such as Japanese.
+    WordBoundary,
+}
+
+/// Policy for a closing quotation mark that does not correspond to the innermost open quotation,
+/// which often arises from broken brackets in OCR'd texts.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuoteMismatchPolicy {
+    /// Ignores the closing mark.
+    #[default]
+    Skip,
+    /// Closes the innermost open quotation with the same pair if any,
+    /// discarding the quotations opened inside it, and ignores the closing mark otherwise.
+    PopToMatch,
+    /// Discards all the open quotations.
+    Reset,
+}
+
+/// Normalization applied to no-break words and input texts before matching the words,
+/// which does not modify the original texts.
+///
+/// Normalization is applied to each character together with its following characters
+/// to be composed, such as combining characters, and a word matches only if its boundaries
+/// coincide with those of the characters in the original text.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WordNormalization {
+    /// Matches words exactly.
+    #[default]
+    None,
+    /// Folds full-width ASCII variants such as `Ｍｒ．` into ASCII characters,
+    /// the ideographic space into the ASCII space, and half-width katakana such as `ｶﾞ`
+    /// into full-width ones.
+    FoldWidth,
+    /// Compares words in Unicode Normalization Form KC, which also folds the widths.
+    /// This requires the `nfkc` feature.
+    #[cfg(feature = "nfkc")]
+    Nfkc,
+}
+
+/// Condition on the character adjacent to a no-break word or a delimiter,
+/// which is checked in the original text.
+///
+/// The condition is always satisfied at the beginning and the end of a text.
+/// In rule files, the condition is written as `"any"`, `"non_alphanumeric"`,
+/// or a character class such as `"[^a-z]"`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum WordBoundary {
+    /// Accepts any character.
+    #[default]
+    Any,
+    /// Accepts a character that is not alphanumeric in Unicode,
+    /// so that `St.` does not match in `1st.`.
+    NonAlphanumeric,
+    /// Accepts a character in the class written in the regex syntax such as `[^a-z]`.
+    Class(String),
+}
+
+impl WordBoundary {
+    /// Compiles the condition into a regex matching an accepted character,
+    /// or returns `None` if all characters are accepted.
+    pub(crate) fn to_regex(&self) -> Result<Option<Regex>> {
+        let class = match self {
+            Self::Any => return Ok(None),
+            Self::NonAlphanumeric => r"[^\p{Alphabetic}\p{N}]",
+            Self::Class(class) => class,
+        };
+        class_regex(class).map(Some)
+    }
+}
+
+/// Compiles a character class such as `[^a-z]` into a regex matching a character in it.
+fn class_regex(class: &str) -> Result<Regex> {
+    if !class.starts_with('[') {
+        return Err(EasySegmenterError::input(format!(
+            "The character class {class:?} must be enclosed in brackets."
+        )));
+    }
+    Regex::new(&format!("^(?:{class})$")).map_err(|e| {
+        EasySegmenterError::input(format!("The character class {class:?} is invalid: {e}"))
+    })
+}
+
+impl TryFrom<String> for WordBoundary {
+    type Error = EasySegmenterError;
+
+    fn try_from(value: String) -> Result<Self> {
+        let boundary = match value.as_str() {
+            "any" => Self::Any,
+            "non_alphanumeric" => Self::NonAlphanumeric,
+            _ => Self::Class(value),
+        };
+        boundary.to_regex()?;
+        Ok(boundary)
+    }
+}
+
+impl From<WordBoundary> for String {
+    fn from(boundary: WordBoundary) -> Self {
+        match boundary {
+            WordBoundary::Any => "any".to_string(),
+            WordBoundary::NonAlphanumeric => "non_alphanumeric".to_string(),
+            WordBoundary::Class(class) => class,
+        }
+    }
+}
+
+/// Automaton to match no-break words, which trades memory usage for speed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WordMatcherKind {
+    /// Uses a DFA for up to 5,000 words and an NFA for more words.
+    #[default]
+    Auto,
+    /// Uses a DFA, which is the fastest but can consume a large amount of memory
+    /// for large dictionaries.
+    Dfa,
+    /// Uses an NFA, which is slower than a DFA but much more memory-efficient,
+    /// suitable for dictionaries with tens of thousands of words.
+    Nfa,
+}
+
+/// Characters trimmed from both ends of each sentence,
+/// which only adjusts the resulting ranges and does not modify the original texts.
+///
+/// In rule files, the policy is written as `"none"`, `"ascii"`, `"unicode"`,
+/// or a character class such as `"[ \t　]"`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum TrimPolicy {
+    /// Does not trim sentences.
+    #[default]
+    None,
+    /// Trims ASCII whitespace such as spaces, tabs, and newlines.
+    Ascii,
+    /// Trims Unicode whitespace, which also includes the ideographic space `　`.
+    Unicode,
+    /// Trims characters in the class written in the regex syntax such as `[ 　]`.
+    Class(String),
+}
+
+impl TrimPolicy {
+    /// Compiles the policy into the set of characters to be trimmed.
+    pub(crate) fn to_trim_chars(&self) -> Result<TrimChars> {
+        Ok(match self {
+            Self::None => TrimChars::None,
+            Self::Ascii => TrimChars::Ascii,
+            Self::Unicode => TrimChars::Unicode,
+            Self::Class(class) => TrimChars::Class(class_regex(class)?),
+        })
+    }
+}
+
+impl TryFrom<String> for TrimPolicy {
+    type Error = EasySegmenterError;
+
+    fn try_from(value: String) -> Result<Self> {
+        let policy = match value.as_str() {
+            "none" => Self::None,
+            "ascii" => Self::Ascii,
+            "unicode" => Self::Unicode,
+            _ => Self::Class(value),
+        };
+        policy.to_trim_chars()?;
+        Ok(policy)
+    }
+}
+
+impl From<TrimPolicy> for String {
+    fn from(policy: TrimPolicy) -> Self {
+        match policy {
+            TrimPolicy::None => "none".to_string(),
+            TrimPolicy::Ascii => "ascii".to_string(),
+            TrimPolicy::Unicode => "unicode".to_string(),
+            TrimPolicy::Class(class) => class,
+        }
+    }
+}
+
+/// Policy to break sentences inside a quotation.
+///
+/// In rule files, the policy is written as `"protect"`, `"split_inside"`,
+/// or `{ split_if_longer_than = N }`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum QuotePolicy {
+    /// Does not break sentences inside the quotation.
+    #[default]
+    Protect,
+    /// Breaks sentences inside the quotation except immediately before the closing mark,
+    /// so that the closing mark is attached to the last sentence in the quotation.
+    SplitInside,
+    /// Breaks sentences inside the quotation in the same manner as [`Self::SplitInside`]
+    /// only if the quotation has more characters than the given number, including the marks.
+    SplitIfLongerThan(usize),
+}
+
+// The policies are (de)serialized manually since the TOML serializer does not support
+// newtype variants and the TOML deserializer does not accept them in non-inline tables.
+impl serde::Serialize for QuotePolicy {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Self::Protect => serializer.serialize_str("protect"),
+            Self::SplitInside => serializer.serialize_str("split_inside"),
+            Self::SplitIfLongerThan(len) => {
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry("split_if_longer_than", len)?;
+                map.end()
+            }
+        }
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for QuotePolicy {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(QuotePolicyVisitor)
+    }
+}
+
+struct QuotePolicyVisitor;
+
+impl<'de> Visitor<'de> for QuotePolicyVisitor {
+    type Value = QuotePolicy;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str(r#""protect", "split_inside", or { split_if_longer_than = N }"#)
+    }
+
+    fn visit_str<E>(self, v: &str) -> std::result::Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        match v {
+            "protect" => Ok(QuotePolicy::Protect),
+            "split_inside" => Ok(QuotePolicy::SplitInside),
+            _ => Err(E::unknown_variant(v, &["protect", "split_inside"])),
+        }
+    }
+
+    fn visit_map<A>(self, mut map: A) -> std::result::Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let policy = match map.next_key::<String>()?.as_deref() {
+            Some("split_if_longer_than") => QuotePolicy::SplitIfLongerThan(map.next_value()?),
+            Some(key) => return Err(de::Error::unknown_field(key, &["split_if_longer_than"])),
+            None => return Err(de::Error::invalid_length(0, &self)),
+        };
+        if map.next_key::<String>()?.is_some() {
+            return Err(de::Error::invalid_length(2, &self));
+        }
+        Ok(policy)
+    }
+}
+
+impl SymmetricQuoteRule {
+    /// Checks if a mark between `prev` and `next` can open a quotation.
+    pub(crate) fn can_open(self, prev: Option<char>, next: Option<char>) -> bool {
+        match self {
+            Self::Toggle => true,
+            Self::WordBoundary => {
+                !prev.is_some_and(|c| c.is_ascii_alphanumeric())
+                    && next.is_some_and(|c| !c.is_whitespace())
+            }
+        }
+    }
+
+    /// Checks if a mark between `prev` and `next` can close a quotation.
+    pub(crate) fn can_close(self, prev: Option<char>, next: Option<char>) -> bool {
+        match self {
+            Self::Toggle => true,
+            Self::WordBoundary => {
+                prev.is_some_and(|c| !c.is_whitespace())
+                    && !next.is_some_and(|c| c.is_ascii_alphanumeric())
+            }
+        }
+    }
+}
+
 /// Builder of [`Segmenter`] to define segmentation rules.
 /// This class allows rules to be defined from scratch.
 /// You can also use template rules in [`crate::template`].
@@ -17,13 +329,48 @@ pub const DEFAULT_MAX_QUOTE_LEVEL: usize = 3;
 /// Delimiters are detected with exact string matching for a set of patterns.
 /// If multiple delimiters are overlapped at a position,
 /// the [leftmost-longest one](https://docs.rs/aho-corasick/latest/aho_corasick/enum.MatchKind.html#variant.LeftmostLongest) is detected.
+///
+/// # Precedence of rules
+///
+/// A text is broken at the positions given by the rules in the following precedence:
+///
+/// 1. Forced breaks by [`Self::force_break_words`] and [`Self::force_break_regex`]
+///    are always applied, even inside quotations and no-break words.
+/// 2. Delimiters are ignored if the positions are protected by quotations,
+///    [`Self::no_break_words`], or [`Self::no_break_regex`].
+/// 3. The other delimiters break the text.
 pub struct SegmenterBuilder {
     in_delimiters: Vec<String>,
     ex_delimiters: Vec<String>,
-    quotes: Vec<(char, char)>,
-    words: Vec<String>,
-    regexes: Vec<Regex>,
+    // Delimiters with the conditions on the preceding and following characters.
+    in_bounded_delimiters: Vec<(String, WordBoundary, WordBoundary)>,
+    ex_bounded_delimiters: Vec<(String, WordBoundary, WordBoundary)>,
+    // Regex patterns for delimiters with their names in rule files.
+    in_delimiter_regexes: Vec<(Option<String>, Regex)>,
+    ex_delimiter_regexes: Vec<(Option<String>, Regex)>,
+    mergeable_delimiters: Vec<String>,
+    trailing_attachers: Vec<String>,
+    quotes: Vec<(String, String)>,
+    // Words with the conditions on the preceding and following characters.
+    words: Vec<(String, WordBoundary, WordBoundary)>,
+    word_patterns: Vec<String>,
+    // Regex patterns with their names in rule files.
+    regexes: Vec<(Option<String>, Regex)>,
+    force_break_words: Vec<String>,
+    // Regex patterns for forced breaks with their names in rule files.
+    force_break_regexes: Vec<(Option<String>, Regex)>,
     max_quote_level: usize,
+    max_quote_len: Option<usize>,
+    quote_reset_delimiters: Vec<String>,
+    quote_mismatch_policy: QuoteMismatchPolicy,
+    // Policies with the opening marks of quotations.
+    quote_policies: Vec<(String, QuotePolicy)>,
+    symmetric_quote_rule: SymmetricQuoteRule,
+    word_ascii_case_insensitive: bool,
+    word_normalization: WordNormalization,
+    word_matcher_kind: WordMatcherKind,
+    trim_policy: TrimPolicy,
+    drop_empty_sentences: bool,
 }
 
 impl SegmenterBuilder {
@@ -32,37 +379,530 @@ impl SegmenterBuilder {
         Self {
             in_delimiters: vec![],
             ex_delimiters: vec![],
+            in_bounded_delimiters: vec![],
+            ex_bounded_delimiters: vec![],
+            in_delimiter_regexes: vec![],
+            ex_delimiter_regexes: vec![],
+            mergeable_delimiters: vec![],
+            trailing_attachers: vec![],
             quotes: vec![],
             words: vec![],
+            word_patterns: vec![],
             regexes: vec![],
+            force_break_words: vec![],
+            force_break_regexes: vec![],
             max_quote_level: DEFAULT_MAX_QUOTE_LEVEL,
+            max_quote_len: None,
+            quote_reset_delimiters: vec![],
+            quote_mismatch_policy: QuoteMismatchPolicy::Skip,
+            quote_policies: vec![],
+            symmetric_quote_rule: SymmetricQuoteRule::Toggle,
+            word_ascii_case_insensitive: false,
+            word_normalization: WordNormalization::None,
+            word_matcher_kind: WordMatcherKind::Auto,
+            trim_policy: TrimPolicy::None,
+            drop_empty_sentences: false,
+        }
+    }
+
+    /// Creates an instance from segmentation rules in the TOML format.
+    /// See [`RuleConfig::from_toml_str`] for the format.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use easy_segmenter::SegmenterBuilder;
+    ///
+    /// let seg = SegmenterBuilder::from_toml_str(r#"
+    ///     in_delimiters = ["。"]
+    ///     quotes = ["「」"]
+    /// "#)
+    /// .unwrap()
+    /// .build()
+    /// .unwrap();
+    /// let text = "私は「はい。そうです。」と答えた。";
+    /// let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+    /// let expected = vec!["私は「はい。そうです。」と答えた。"];
+    /// assert_eq!(sentences, expected);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// An error will arise when the deserialization fails or
+    /// when a rule is invalid (see [`Self::from_rule_config`]).
+    pub fn from_toml_str<S>(toml_str: S) -> Result<Self>
+    where
+        S: AsRef<str>,
+    {
+        Self::from_rule_config(RuleConfig::from_toml_str(toml_str)?)
+    }
+
+    /// Creates an instance from a rule file in the TOML format.
+    /// See [`RuleConfig::from_toml_str`] for the format.
+    ///
+    /// Relative paths in `extends` are resolved from the directory of the file.
+    ///
+    /// # Errors
+    ///
+    /// An error will arise when the file cannot be read, when the deserialization fails,
+    /// or when a rule is invalid (see [`Self::from_rule_config`]).
+    pub fn from_toml_file<P>(path: P) -> Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let base_dir = path.parent().unwrap_or_else(|| Path::new(""));
+        Self::from_rule_config(RuleConfig::from_toml_file(path)?.resolve(base_dir)?)
+    }
+
+    /// Creates an instance from a [`RuleConfig`].
+    ///
+    /// `extends`, `remove_words`, and `remove_delimiters` are resolved through
+    /// [`RuleConfig::resolve`], where relative paths are resolved from the current directory.
+    ///
+    /// # Errors
+    ///
+    /// An error will arise when the resolution fails, or
+    /// with the key of the invalid rule when
+    ///  - a delimiter, a mergeable delimiter, a trailing attacher, a word,
+    ///    or a quote reset delimiter is empty,
+    ///  - an entry in `quotes` does not consist of exactly two characters,
+    ///  - a mark in `quote_pairs` is empty,
+    ///  - brackets in a word pattern are unbalanced, nested, or empty,
+    ///  - a quotation mark is duplicated in different quotations,
+    ///  - an opening mark in `quote_policies` is not defined in the quotations,
+    ///  - a regex pattern cannot be compiled, or
+    ///  - `max_quote_level` or `max_quote_len` is zero.
+    pub fn from_rule_config(config: RuleConfig) -> Result<Self> {
+        let config = config.resolve("")?;
+        let mut builder = Self::new();
+        for (key, patterns) in [
+            ("in_delimiters", &config.in_delimiters),
+            ("ex_delimiters", &config.ex_delimiters),
+            ("mergeable_delimiters", &config.mergeable_delimiters),
+            ("trailing_attachers", &config.trailing_attachers),
+            ("words", &config.words),
+            ("word_patterns", &config.word_patterns),
+            ("quote_reset_delimiters", &config.quote_reset_delimiters),
+            ("force_break_words", &config.force_break_words),
+        ] {
+            for (i, p) in patterns.iter().enumerate() {
+                if p.is_empty() {
+                    return Err(rule_error(key, i, p, "The entry must not be empty."));
+                }
+            }
+        }
+        for (i, w) in config.word_patterns.iter().enumerate() {
+            matcher::parse_word(w).map_err(|e| rule_error("word_patterns", i, w, e))?;
+        }
+        for (g, group) in config.bounded_words.iter().enumerate() {
+            for (i, w) in group.words.iter().enumerate() {
+                if w.is_empty() {
+                    let key = format!("bounded_words[{g}].words");
+                    return Err(rule_error(&key, i, w, "The entry must not be empty."));
+                }
+            }
+        }
+        for (g, group) in config.bounded_delimiters.iter().enumerate() {
+            for (name, delimiters) in [
+                ("in_delimiters", &group.in_delimiters),
+                ("ex_delimiters", &group.ex_delimiters),
+            ] {
+                let key = format!("bounded_delimiters[{g}].{name}");
+                for (i, d) in delimiters.iter().enumerate() {
+                    if d.is_empty() {
+                        return Err(rule_error(&key, i, d, "The entry must not be empty."));
+                    }
+                }
+            }
+        }
+        let mut quote_marks = vec![];
+        let mut push_quote = |key, i, entry: &str, open: String, close: String| {
+            if open.is_empty() || close.is_empty() {
+                return Err(rule_error(key, i, entry, "The marks must not be empty."));
+            }
+            // The two marks of a symmetric quotation are the same.
+            let marks = if open == close {
+                vec![&open]
+            } else {
+                vec![&open, &close]
+            };
+            for m in marks {
+                if quote_marks.contains(m) {
+                    return Err(rule_error(
+                        key,
+                        i,
+                        entry,
+                        format!("The mark {m:?} is duplicated."),
+                    ));
+                }
+                quote_marks.push(m.clone());
+            }
+            builder.quotes.push((open, close));
+            Ok(())
+        };
+        for (i, q) in config.quotes.iter().enumerate() {
+            let chars: Vec<_> = q.chars().collect();
+            if chars.len() != 2 {
+                return Err(rule_error(
+                    "quotes",
+                    i,
+                    q,
+                    "The entry must consist of exactly two characters.",
+                ));
+            }
+            push_quote("quotes", i, q, chars[0].to_string(), chars[1].to_string())?;
+        }
+        for (i, (open, close)) in config.quote_pairs.iter().enumerate() {
+            let entry = format!("{open} {close}");
+            push_quote("quote_pairs", i, &entry, open.clone(), close.clone())?;
+        }
+        for (open, policy) in &config.quote_policies {
+            if !builder.quotes.iter().any(|(o, _)| o == open) {
+                return Err(EasySegmenterError::input(format!(
+                    "Invalid rule in quote_policies.{open:?} = {policy:?}: \
+                     The opening mark is not defined in the quotations."
+                )));
+            }
+            builder.quote_policies.push((open.clone(), *policy));
+        }
+        builder.regexes = compile_regex_table("regex", &config.regex)?;
+        builder.in_delimiter_regexes =
+            compile_regex_table("in_delimiter_regex", &config.in_delimiter_regex)?;
+        builder.ex_delimiter_regexes =
+            compile_regex_table("ex_delimiter_regex", &config.ex_delimiter_regex)?;
+        builder.force_break_regexes =
+            compile_regex_table("force_break_regex", &config.force_break_regex)?;
+        if let Some(max_quote_level) = config.max_quote_level {
+            builder = builder.max_quote_level(max_quote_level).map_err(|e| {
+                EasySegmenterError::input(format!(
+                    "Invalid rule in max_quote_level = {max_quote_level}: {e}"
+                ))
+            })?;
+        }
+        if let Some(max_quote_len) = config.max_quote_len {
+            builder = builder.max_quote_len(max_quote_len).map_err(|e| {
+                EasySegmenterError::input(format!(
+                    "Invalid rule in max_quote_len = {max_quote_len}: {e}"
+                ))
+            })?;
         }
+        if let Some(policy) = config.quote_mismatch_policy {
+            builder.quote_mismatch_policy = policy;
+        }
+        if let Some(rule) = config.symmetric_quote_rule {
+            builder.symmetric_quote_rule = rule;
+        }
+        if let Some(yes) = config.word_ascii_case_insensitive {
+            builder.word_ascii_case_insensitive = yes;
+        }
+        if let Some(normalization) = config.word_normalization {
+            builder.word_normalization = normalization;
+        }
+        if let Some(kind) = config.word_matcher_kind {
+            builder.word_matcher_kind = kind;
+        }
+        if let Some(policy) = config.trim_policy {
+            builder.trim_policy = policy;
+        }
+        if let Some(yes) = config.drop_empty_sentences {
+            builder.drop_empty_sentences = yes;
+        }
+        builder.in_delimiters = config.in_delimiters;
+        builder.ex_delimiters = config.ex_delimiters;
+        builder.mergeable_delimiters = config.mergeable_delimiters;
+        builder.trailing_attachers = config.trailing_attachers;
+        for group in config.bounded_delimiters {
+            builder = builder
+                .in_delimiters_with_boundaries(
+                    group.in_delimiters,
+                    group.before.clone(),
+                    group.after.clone(),
+                )
+                .ex_delimiters_with_boundaries(group.ex_delimiters, group.before, group.after);
+        }
+        builder.words = config
+            .words
+            .into_iter()
+            .map(|w| (w, WordBoundary::Any, WordBoundary::Any))
+            .collect();
+        for group in config.bounded_words {
+            for w in group.words {
+                builder
+                    .words
+                    .push((w, group.before.clone(), group.after.clone()));
+            }
+        }
+        builder.word_patterns = config.word_patterns;
+        builder.quote_reset_delimiters = config.quote_reset_delimiters;
+        builder.force_break_words = config.force_break_words;
+        Ok(builder)
+    }
+
+    /// Exports the segmentation rules into a [`RuleConfig`].
+    ///
+    /// Regex patterns added through [`Self::in_delimiter_regex`], [`Self::ex_delimiter_regex`],
+    /// [`Self::no_break_regex`], and [`Self::force_break_regex`] are named `regex0`, `regex1`,
+    /// and so on, in the order of registration, where the indices are zero-padded
+    /// such as `regex00` if there are more than ten patterns.
+    /// Only [`Regex::as_str`] is exported, so options given by [`regex::RegexBuilder`],
+    /// e.g., case insensitivity, are lost unless they are written as inline flags such as `(?i)`.
+    pub fn to_rule_config(&self) -> RuleConfig {
+        // Pairs of single characters are written in the compact form.
+        let (quotes, quote_pairs): (Vec<_>, Vec<_>) = self
+            .quotes
+            .iter()
+            .cloned()
+            .partition(|(open, close)| open.chars().count() == 1 && close.chars().count() == 1);
+        let quotes = quotes
+            .into_iter()
+            .map(|(open, close)| format!("{open}{close}"))
+            .collect();
+        // Words with conditions are grouped by the conditions in the order of appearance.
+        let mut words = vec![];
+        let mut bounded_words: Vec<BoundedWords> = vec![];
+        for (w, before, after) in &self.words {
+            if *before == WordBoundary::Any && *after == WordBoundary::Any {
+                words.push(w.clone());
+            } else if let Some(group) = bounded_words
+                .iter_mut()
+                .find(|g| g.before == *before && g.after == *after)
+            {
+                group.words.push(w.clone());
+            } else {
+                bounded_words.push(BoundedWords {
+                    words: vec![w.clone()],
+                    before: before.clone(),
+                    after: after.clone(),
+                });
+            }
+        }
+        // Delimiters with conditions are grouped in the same manner as words.
+        let mut bounded_delimiters: Vec<BoundedDelimiters> = vec![];
+        let in_bounded = self.in_bounded_delimiters.iter().map(|d| (d, true));
+        let ex_bounded = self.ex_bounded_delimiters.iter().map(|d| (d, false));
+        for ((d, before, after), is_in_delimiter) in in_bounded.chain(ex_bounded) {
+            let idx = bounded_delimiters
+                .iter()
+                .position(|g| g.before == *before && g.after == *after)
+                .unwrap_or_else(|| {
+                    bounded_delimiters.push(BoundedDelimiters {
+                        in_delimiters: vec![],
+                        ex_delimiters: vec![],
+                        before: before.clone(),
+                        after: after.clone(),
+                    });
+                    bounded_delimiters.len() - 1
+                });
+            let group = &mut bounded_delimiters[idx];
+            if is_in_delimiter {
+                group.in_delimiters.push(d.clone());
+            } else {
+                group.ex_delimiters.push(d.clone());
+            }
+        }
+        RuleConfig {
+            in_delimiters: self.in_delimiters.clone(),
+            ex_delimiters: self.ex_delimiters.clone(),
+            mergeable_delimiters: self.mergeable_delimiters.clone(),
+            trailing_attachers: self.trailing_attachers.clone(),
+            quotes,
+            quote_pairs,
+            words,
+            word_patterns: self.word_patterns.clone(),
+            bounded_words,
+            bounded_delimiters,
+            max_quote_level: (self.max_quote_level != DEFAULT_MAX_QUOTE_LEVEL)
+                .then_some(self.max_quote_level),
+            max_quote_len: self.max_quote_len,
+            quote_reset_delimiters: self.quote_reset_delimiters.clone(),
+            quote_mismatch_policy: (self.quote_mismatch_policy != QuoteMismatchPolicy::default())
+                .then_some(self.quote_mismatch_policy),
+            quote_policies: self.quote_policies.iter().cloned().collect(),
+            symmetric_quote_rule: (self.symmetric_quote_rule != SymmetricQuoteRule::default())
+                .then_some(self.symmetric_quote_rule),
+            word_ascii_case_insensitive: self.word_ascii_case_insensitive.then_some(true),
+            word_normalization: (self.word_normalization != WordNormalization::default())
+                .then_some(self.word_normalization),
+            word_matcher_kind: (self.word_matcher_kind != WordMatcherKind::default())
+                .then_some(self.word_matcher_kind),
+            trim_policy: (self.trim_policy != TrimPolicy::default())
+                .then(|| self.trim_policy.clone()),
+            drop_empty_sentences: self.drop_empty_sentences.then_some(true),
+            regex: regex_table(&self.regexes),
+            in_delimiter_regex: regex_table(&self.in_delimiter_regexes),
+            ex_delimiter_regex: regex_table(&self.ex_delimiter_regexes),
+            force_break_words: self.force_break_words.clone(),
+            force_break_regex: regex_table(&self.force_break_regexes),
+            ..RuleConfig::default()
+        }
+    }
+
+    /// Exports the segmentation rules into a string in the TOML format,
+    /// which can be loaded through [`Self::from_toml_str`].
+    /// See [`Self::to_rule_config`] for the names and options of regex patterns.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use regex::Regex;
+    /// use easy_segmenter::SegmenterBuilder;
+    ///
+    /// let toml_str = SegmenterBuilder::new()
+    ///     .in_delimiters(["。"])
+    ///     .quotes([('「', '」')])
+    ///     .no_break_regex(Regex::new(r"(。{2,})。").unwrap())
+    ///     .to_toml_string()
+    ///     .unwrap();
+    /// let expected = r#"in_delimiters = ["。"]
+    /// ex_delimiters = []
+    /// quotes = ["「」"]
+    /// words = []
+    ///
+    /// [regex]
+    /// regex0 = "(。{2,})。"
+    /// "#;
+    /// assert_eq!(toml_str, expected);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// [`toml::ser::Error`] will be reported if the serialization fails.
+    pub fn to_toml_string(&self) -> Result<String> {
+        self.to_rule_config().to_toml_string()
     }
 
     /// Compiles the segmenter.
+    ///
+    /// # Errors
+    ///
+    /// An error will arise when
+    ///  - both `in_delimiters` and `ex_delimiters` are empty,
+    ///  - a delimiter is empty,
+    ///  - a quotation mark is empty or duplicated,
+    ///  - an opening mark given to [`Self::quote_policy`] is not defined in the quotations,
+    ///  - brackets in a word pattern are unbalanced, nested, or empty,
+    ///  - a character class in [`WordBoundary::Class`] is invalid,
+    ///  - a string given to [`Self::mergeable_delimiters`] or [`Self::trailing_attachers`]
+    ///    is empty, or
+    ///  - a word given to [`Self::force_break_words`] is empty.
     pub fn build(self) -> Result<Segmenter> {
-        if self.in_delimiters.is_empty() && self.ex_delimiters.is_empty() {
+        if self.in_delimiters.is_empty()
+            && self.ex_delimiters.is_empty()
+            && self.in_bounded_delimiters.is_empty()
+            && self.ex_bounded_delimiters.is_empty()
+            && self.in_delimiter_regexes.is_empty()
+            && self.ex_delimiter_regexes.is_empty()
+        {
             return Err(EasySegmenterError::input(
                 "Both in_ and ex_delimiters must not be empty.",
             ));
         }
-        let delimiter_matcher = DelimiterMatcher::new(&self.in_delimiters, &self.ex_delimiters);
+        let bounded_delimiters = self
+            .in_bounded_delimiters
+            .iter()
+            .chain(&self.ex_bounded_delimiters);
+        if self
+            .in_delimiters
+            .iter()
+            .chain(&self.ex_delimiters)
+            .any(String::is_empty)
+            || bounded_delimiters.map(|(d, _, _)| d).any(String::is_empty)
+        {
+            return Err(EasySegmenterError::input("Delimiters must not be empty."));
+        }
+        if self.mergeable_delimiters.iter().any(String::is_empty) {
+            return Err(EasySegmenterError::input(
+                "Mergeable delimiters must not be empty.",
+            ));
+        }
+        if self.trailing_attachers.iter().any(String::is_empty) {
+            return Err(EasySegmenterError::input(
+                "Trailing attachers must not be empty.",
+            ));
+        }
+        let delimiter_regexes = self
+            .in_delimiter_regexes
+            .iter()
+            .map(|(_, re)| (re.clone(), true))
+            .chain(
+                self.ex_delimiter_regexes
+                    .iter()
+                    .map(|(_, re)| (re.clone(), false)),
+            )
+            .collect();
+        let delimiter_matcher = DelimiterMatcher::new(
+            &self.in_delimiters,
+            &self.ex_delimiters,
+            &self.in_bounded_delimiters,
+            &self.ex_bounded_delimiters,
+            delimiter_regexes,
+            &self.mergeable_delimiters,
+            &self.trailing_attachers,
+        )?;
         let quote_matcher = if self.quotes.is_empty() {
             None
         } else {
-            Some(QuoteMatcher::new(&self.quotes)?)
+            Some(QuoteMatcher::new(
+                &self.quotes,
+                &self.quote_reset_delimiters,
+            )?)
+        };
+        let mut quote_policies = vec![QuotePolicy::Protect; self.quotes.len()];
+        for (open, policy) in &self.quote_policies {
+            let id = self
+                .quotes
+                .iter()
+                .position(|(o, _)| o == open)
+                .ok_or_else(|| {
+                    EasySegmenterError::input(format!(
+                        "The opening mark {open:?} is not defined in the quotations."
+                    ))
+                })?;
+            quote_policies[id] = *policy;
+        }
+        let word_matcher = if self.words.is_empty() && self.word_patterns.is_empty() {
+            None
+        } else {
+            Some(WordMatcher::new(
+                &self.words,
+                &self.word_patterns,
+                self.word_ascii_case_insensitive,
+                self.word_normalization,
+                self.word_matcher_kind,
+            )?)
         };
-        let word_matcher = if self.words.is_empty() {
+        if self.force_break_words.iter().any(String::is_empty) {
+            return Err(EasySegmenterError::input(
+                "Force-break words must not be empty.",
+            ));
+        }
+        let force_break_matcher = if self.force_break_words.is_empty() {
             None
         } else {
-            Some(WordMatcher::new(&self.words))
+            Some(ForceBreakMatcher::new(&self.force_break_words))
         };
         Ok(Segmenter::new(
             delimiter_matcher,
             quote_matcher,
             word_matcher,
-            self.regexes,
-            self.max_quote_level,
+            self.regexes.into_iter().map(|(_, re)| re).collect(),
+            force_break_matcher,
+            self.force_break_regexes
+                .into_iter()
+                .map(|(_, re)| re)
+                .collect(),
+            QuoteOptions {
+                max_level: self.max_quote_level,
+                max_len: self.max_quote_len,
+                mismatch_policy: self.quote_mismatch_policy,
+                symmetric_rule: self.symmetric_quote_rule,
+                policies: quote_policies,
+            },
+            TrimOptions {
+                chars: self.trim_policy.to_trim_chars()?,
+                drop_empty: self.drop_empty_sentences,
+            },
         ))
     }
 
@@ -122,68 +962,109 @@ impl SegmenterBuilder {
         self
     }
 
-    /// Adds pairs of characters to specify quotations.
-    /// Sentences within a quotation will not be broken.
+    /// Adds delimiters that are included in resulting sentences and break texts only if
+    /// the preceding and following characters satisfy the conditions.
     ///
-    /// `quotes` must not have duplicate entries.
+    /// The conditions are checked only at the positions of the delimiters,
+    /// which is much faster than [`Self::in_delimiter_regex`] for the same purpose.
+    /// If overlapped, the leftmost-longest one of the delimiters satisfying the conditions
+    /// is detected in the same manner as [`Self::in_delimiters`].
     ///
     /// # Examples
     ///
     /// ```
+    /// use easy_segmenter::segmenter::builder::WordBoundary;
     /// use easy_segmenter::SegmenterBuilder;
     ///
     /// let seg = SegmenterBuilder::new()
-    ///     .in_delimiters(["。"])
-    ///     .quotes([('「', '」')])
+    ///     .in_delimiters_with_boundaries(
+    ///         ["。"],
+    ///         WordBoundary::Any,
+    ///         WordBoundary::Class("[^」）]".to_string()),
+    ///     )
+    ///     .in_delimiters_with_boundaries(
+    ///         [". "],
+    ///         WordBoundary::Any,
+    ///         WordBoundary::Class("[A-Z]".to_string()),
+    ///     )
     ///     .build()
     ///     .unwrap();
-    /// let text = "私は「はい。そうです。」と答えた。";
+    /// let text = "はい。）いいえ。It costs 3. 5 dollars. Is it cheap?";
     /// let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
-    /// let expected = vec!["私は「はい。そうです。」と答えた。"];
+    /// let expected = vec!["はい。）いいえ。", "It costs 3. 5 dollars. ", "Is it cheap?"];
     /// assert_eq!(sentences, expected);
     /// ```
-    pub fn quotes<I>(mut self, quotes: I) -> Self
+    pub fn in_delimiters_with_boundaries<I, P>(
+        mut self,
+        delimiters: I,
+        before: WordBoundary,
+        after: WordBoundary,
+    ) -> Self
     where
-        I: IntoIterator<Item = (char, char)>,
+        I: IntoIterator<Item = P>,
+        P: AsRef<str>,
     {
-        quotes.into_iter().for_each(|p| self.quotes.push(p));
+        delimiters
+            .into_iter()
+            .map(|p| p.as_ref().to_string())
+            .for_each(|p| {
+                self.in_bounded_delimiters
+                    .push((p, before.clone(), after.clone()));
+            });
         self
     }
 
-    /// Adds words that should not be broken.
+    /// Adds delimiters that are excluded in resulting sentences and break texts only if
+    /// the preceding and following characters satisfy the conditions.
+    ///
+    /// See [`Self::in_delimiters_with_boundaries`] for how the conditions are checked.
     ///
     /// # Examples
     ///
     /// ```
+    /// use easy_segmenter::segmenter::builder::WordBoundary;
     /// use easy_segmenter::SegmenterBuilder;
     ///
     /// let seg = SegmenterBuilder::new()
-    ///     .in_delimiters(["。"])
-    ///     .no_break_words(["モーニング娘。"])
+    ///     .ex_delimiters_with_boundaries(
+    ///         ["\n"],
+    ///         WordBoundary::Class("[。！？]".to_string()),
+    ///         WordBoundary::Any,
+    ///     )
     ///     .build()
     ///     .unwrap();
-    /// let text = "モーニング娘。の新曲";
+    /// let text = "これは\nペンです。\nそれはマーカーです。";
     /// let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
-    /// let expected = vec!["モーニング娘。の新曲"];
+    /// let expected = vec!["これは\nペンです。", "それはマーカーです。"];
     /// assert_eq!(sentences, expected);
     /// ```
-    pub fn no_break_words<I, P>(mut self, words: I) -> Self
+    pub fn ex_delimiters_with_boundaries<I, P>(
+        mut self,
+        delimiters: I,
+        before: WordBoundary,
+        after: WordBoundary,
+    ) -> Self
     where
         I: IntoIterator<Item = P>,
         P: AsRef<str>,
     {
-        words
+        delimiters
             .into_iter()
-            .map(|w| w.as_ref().to_string())
-            .for_each(|w| self.words.push(w));
+            .map(|p| p.as_ref().to_string())
+            .for_each(|p| {
+                self.ex_bounded_delimiters
+                    .push((p, before.clone(), after.clone()));
+            });
         self
     }
 
-    /// Adds regex patterns that should not be broken.
-    /// Captured patterns will not be broken.
+    /// Adds a regex pattern for delimiters included in resulting sentences.
+    /// The first captured group is the delimiter, or the whole match if the pattern has no group,
+    /// so that the rest of the match can be used as the context.
     ///
-    /// Regular expressions are powerful, but complicated ones can slow down segmentation.
-    /// Consider using [`Self::no_break_words`] first to solve your problem.
+    /// Regex delimiters are merged with [`Self::in_delimiters`] and [`Self::ex_delimiters`]
+    /// in the order of positions, and the leftmost-longest one is detected if overlapped.
+    /// Literal delimiters take priority over regex ones of the same span.
     ///
     /// # Examples
     ///
@@ -192,44 +1073,772 @@ impl SegmenterBuilder {
     /// use easy_segmenter::SegmenterBuilder;
     ///
     /// let seg = SegmenterBuilder::new()
-    ///     .in_delimiters(["．"])
-    ///     .no_break_regex(Regex::new(r"\d(．)\d").unwrap())
+    ///     .in_delimiter_regex(Regex::new(r"(\. )[A-Z]").unwrap())
     ///     .build()
     ///     .unwrap();
-    /// let text = "３．１４";
+    /// let text = "It costs 3. 5 dollars. Is it cheap?";
     /// let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
-    /// let expected = vec!["３．１４"];
+    /// let expected = vec!["It costs 3. 5 dollars. ", "Is it cheap?"];
     /// assert_eq!(sentences, expected);
     /// ```
+    pub fn in_delimiter_regex(mut self, regex: Regex) -> Self {
+        self.in_delimiter_regexes.push((None, regex));
+        self
+    }
+
+    /// Adds a regex pattern for delimiters excluded in resulting sentences.
+    /// The first captured group is the delimiter, or the whole match if the pattern has no group.
     ///
-    /// # Notes
+    /// See [`Self::in_delimiter_regex`] for how regex delimiters are matched.
     ///
-    /// This function takes a single regex pattern, not a sequence of those, because
-    ///  - many regex patterns should be registered as a general rule, and
-    ///  - a single regex can define multiple rules.
-    /// Nonetheless, you can register multiple patterns by repeating this function.
-    pub fn no_break_regex(mut self, regex: Regex) -> Self {
-        self.regexes.push(regex);
+    /// # Examples
+    ///
+    /// ```
+    /// use regex::Regex;
+    /// use easy_segmenter::SegmenterBuilder;
+    ///
+    /// let seg = SegmenterBuilder::new()
+    ///     .ex_delimiter_regex(Regex::new(r"\n{2,}").unwrap())
+    ///     .build()
+    ///     .unwrap();
+    /// let text = "これはペンです\nそれは\n\n\nマーカーです";
+    /// let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+    /// let expected = vec!["これはペンです\nそれは", "マーカーです"];
+    /// assert_eq!(sentences, expected);
+    /// ```
+    pub fn ex_delimiter_regex(mut self, regex: Regex) -> Self {
+        self.ex_delimiter_regexes.push((None, regex));
         self
     }
 
-    /// Sets the maximum nested level of quotations.
-    /// The default value is [`DEFAULT_MAX_QUOTE_LEVEL`].
+    /// Adds inclusive delimiters whose runs such as `！？` are merged into one delimiter.
     ///
-    /// A smaller value will speed up segmentation but
-    /// make it more susceptible to errant pairs of quotation marks.
+    /// Adjacent delimiters are merged only when all of them are given here.
+    /// Strings that are not detected as inclusive delimiters are ignored.
+    /// Delimiters are not merged across forced breaks or protected positions.
     ///
-    /// # Errors
+    /// # Examples
     ///
-    /// An error will arise when `max_quote_level == 0`.
-    pub fn max_quote_level(mut self, max_quote_level: usize) -> Result<Self> {
-        if max_quote_level == 0 {
-            Err(EasySegmenterError::input(
-                "max_quote_level must not be zero.",
-            ))
-        } else {
-            self.max_quote_level = max_quote_level;
-            Ok(self)
-        }
+    /// ```
+    /// use easy_segmenter::SegmenterBuilder;
+    ///
+    /// let seg = SegmenterBuilder::new()
+    ///     .in_delimiters(["。", "！", "？"])
+    ///     .mergeable_delimiters(["。", "！", "？"])
+    ///     .build()
+    ///     .unwrap();
+    /// let text = "本当！？はぁ。。。疲れた。";
+    /// let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+    /// let expected = vec!["本当！？", "はぁ。。。", "疲れた。"];
+    /// assert_eq!(sentences, expected);
+    /// ```
+    pub fn mergeable_delimiters<I, P>(mut self, delimiters: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<str>,
+    {
+        self.mergeable_delimiters
+            .extend(delimiters.into_iter().map(|d| d.as_ref().to_string()));
+        self
+    }
+
+    /// Adds strings such as closing brackets that are attached to the preceding sentence
+    /// when they immediately follow an inclusive delimiter.
+    ///
+    /// Consecutive attachers are all attached, and the longest one is preferred at each position.
+    /// Attachers are not attached across forced breaks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use easy_segmenter::SegmenterBuilder;
+    ///
+    /// let seg = SegmenterBuilder::new()
+    ///     .in_delimiters(["。"])
+    ///     .trailing_attachers(["」", "）", "』"])
+    ///     .build()
+    ///     .unwrap();
+    /// let text = "行った。」と言った。終わり。）』次は";
+    /// let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+    /// let expected = vec!["行った。」", "と言った。", "終わり。）』", "次は"];
+    /// assert_eq!(sentences, expected);
+    /// ```
+    pub fn trailing_attachers<I, P>(mut self, attachers: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<str>,
+    {
+        attachers
+            .into_iter()
+            .map(|a| a.as_ref().to_string())
+            .for_each(|a| self.trailing_attachers.push(a));
+        self
+    }
+
+    /// Adds pairs of opening and closing marks to specify quotations.
+    /// Sentences within a quotation will not be broken.
+    ///
+    /// Each mark is a character such as `'「'` or a non-empty string such as `"<q>"`.
+    /// If marks overlap at a position, the leftmost-longest one is detected,
+    /// so `"<<"` takes precedence over `"<"`.
+    /// `quotes` must not have duplicate marks in different pairs.
+    /// A pair of the same marks such as `('"', '"')` specifies a symmetric quotation,
+    /// whose marks are interpreted by [`Self::symmetric_quote_rule`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use easy_segmenter::SegmenterBuilder;
+    ///
+    /// let seg = SegmenterBuilder::new()
+    ///     .in_delimiters(["。"])
+    ///     .quotes([('「', '」')])
+    ///     .build()
+    ///     .unwrap();
+    /// let text = "私は「はい。そうです。」と答えた。";
+    /// let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+    /// let expected = vec!["私は「はい。そうです。」と答えた。"];
+    /// assert_eq!(sentences, expected);
+    ///
+    /// let seg = SegmenterBuilder::new()
+    ///     .in_delimiters(["。"])
+    ///     .quotes([("<q>", "</q>")])
+    ///     .build()
+    ///     .unwrap();
+    /// let text = "私は<q>はい。そうです。</q>と答えた。";
+    /// let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+    /// let expected = vec!["私は<q>はい。そうです。</q>と答えた。"];
+    /// assert_eq!(sentences, expected);
+    /// ```
+    pub fn quotes<I, P>(mut self, quotes: I) -> Self
+    where
+        I: IntoIterator<Item = (P, P)>,
+        P: Into<String>,
+    {
+        quotes
+            .into_iter()
+            .map(|(open, close)| (open.into(), close.into()))
+            .for_each(|p| self.quotes.push(p));
+        self
+    }
+
+    /// Sets the policy to break sentences inside the quotation with the opening mark `open`,
+    /// which must be added through [`Self::quotes`].
+    /// The default policy is [`QuotePolicy::Protect`].
+    ///
+    /// Sentences broken inside quotations can be associated with the quotations through
+    /// [`Sentence::parent_quote`](crate::Sentence::parent_quote).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use easy_segmenter::segmenter::builder::QuotePolicy;
+    /// use easy_segmenter::SegmenterBuilder;
+    ///
+    /// let seg = SegmenterBuilder::new()
+    ///     .in_delimiters(["。"])
+    ///     .quotes([('「', '」'), ('（', '）')])
+    ///     .quote_policy('「', QuotePolicy::SplitInside)
+    ///     .build()
+    ///     .unwrap();
+    /// let text = "「はい。そうです。」と答えた。（嘘だけど。）";
+    /// let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+    /// let expected = vec!["「はい。", "そうです。」と答えた。", "（嘘だけど。）"];
+    /// assert_eq!(sentences, expected);
+    /// ```
+    pub fn quote_policy<P>(mut self, open: P, policy: QuotePolicy) -> Self
+    where
+        P: Into<String>,
+    {
+        self.quote_policies.push((open.into(), policy));
+        self
+    }
+
+    /// Sets the rule to determine whether a symmetric quotation mark opens or closes
+    /// a quotation. The default value is [`SymmetricQuoteRule::Toggle`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use easy_segmenter::segmenter::builder::SymmetricQuoteRule;
+    /// use easy_segmenter::SegmenterBuilder;
+    ///
+    /// let seg = SegmenterBuilder::new()
+    ///     .in_delimiters([". "])
+    ///     .quotes([('\'', '\'')])
+    ///     .symmetric_quote_rule(SymmetricQuoteRule::WordBoundary)
+    ///     .build()
+    ///     .unwrap();
+    /// let text = "I don't know. He said 'No. Never.' and left. OK.";
+    /// let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+    /// let expected = vec!["I don't know. ", "He said 'No. Never.' and left. ", "OK."];
+    /// assert_eq!(sentences, expected);
+    /// ```
+    pub const fn symmetric_quote_rule(mut self, rule: SymmetricQuoteRule) -> Self {
+        self.symmetric_quote_rule = rule;
+        self
+    }
+
+    /// Adds words that should not be broken.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use easy_segmenter::SegmenterBuilder;
+    ///
+    /// let seg = SegmenterBuilder::new()
+    ///     .in_delimiters(["。"])
+    ///     .no_break_words(["モーニング娘。"])
+    ///     .build()
+    ///     .unwrap();
+    /// let text = "モーニング娘。の新曲";
+    /// let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+    /// let expected = vec!["モーニング娘。の新曲"];
+    /// assert_eq!(sentences, expected);
+    /// ```
+    pub fn no_break_words<I, P>(mut self, words: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<str>,
+    {
+        words
+            .into_iter()
+            .map(|w| w.as_ref().to_string())
+            .for_each(|w| self.words.push((w, WordBoundary::Any, WordBoundary::Any)));
+        self
+    }
+
+    /// Adds words only some parts of which should not be broken.
+    ///
+    /// The protected parts are enclosed in brackets like capture groups in
+    /// [`Self::no_break_regex`]; e.g., `e[.]g.` protects the first dot
+    /// but allows a sentence to end at the last one.
+    /// `\[`, `\]`, and `\\` represent the literal characters,
+    /// and a pattern without brackets is protected entirely as in [`Self::no_break_words`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use easy_segmenter::SegmenterBuilder;
+    ///
+    /// let seg = SegmenterBuilder::new()
+    ///     .in_delimiters(["."])
+    ///     .no_break_word_patterns(["e[.]g."])
+    ///     .build()
+    ///     .unwrap();
+    /// let text = "See e.g.The end.";
+    /// let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+    /// let expected = vec!["See e.g.", "The end."];
+    /// assert_eq!(sentences, expected);
+    /// ```
+    pub fn no_break_word_patterns<I, P>(mut self, patterns: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<str>,
+    {
+        self.word_patterns
+            .extend(patterns.into_iter().map(|p| p.as_ref().to_string()));
+        self
+    }
+
+    /// Adds words that should not be broken only if the preceding and following characters
+    /// satisfy the conditions, e.g., abbreviations in Latin-script languages.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use easy_segmenter::segmenter::builder::WordBoundary;
+    /// use easy_segmenter::SegmenterBuilder;
+    ///
+    /// let seg = SegmenterBuilder::new()
+    ///     .in_delimiters([". "])
+    ///     .no_break_words_with_boundaries(
+    ///         ["St. "],
+    ///         WordBoundary::NonAlphanumeric,
+    ///         WordBoundary::Any,
+    ///     )
+    ///     .build()
+    ///     .unwrap();
+    /// let text = "He came 1st. St. Mary won.";
+    /// let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+    /// let expected = vec!["He came 1st. ", "St. Mary won."];
+    /// assert_eq!(sentences, expected);
+    /// ```
+    pub fn no_break_words_with_boundaries<I, P>(
+        mut self,
+        words: I,
+        before: WordBoundary,
+        after: WordBoundary,
+    ) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<str>,
+    {
+        words
+            .into_iter()
+            .map(|w| w.as_ref().to_string())
+            .for_each(|w| self.words.push((w, before.clone(), after.clone())));
+        self
+    }
+
+    /// Adds words that should not be broken from a file with one word per line.
+    /// See [`Self::no_break_words_from_reader`] for the format.
+    ///
+    /// # Errors
+    ///
+    /// An error will arise when the file cannot be read.
+    pub fn no_break_words_from_file<P>(self, path: P) -> Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let file = File::open(path)?;
+        self.no_break_words_from_reader(BufReader::new(file))
+    }
+
+    /// Adds words that should not be broken from a reader with one word per line,
+    /// which is suitable for large dictionaries.
+    ///
+    /// Lines starting with `#` are comments, and blank lines are ignored.
+    /// Other whitespace is kept as a part of words except for line breaks,
+    /// and a word starting with `#` is written as `\#`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use easy_segmenter::SegmenterBuilder;
+    ///
+    /// let dict = "# Idol groups\nモーニング娘。\n\n\\#Hash。\n";
+    /// let seg = SegmenterBuilder::new()
+    ///     .in_delimiters(["。"])
+    ///     .no_break_words_from_reader(dict.as_bytes())
+    ///     .unwrap()
+    ///     .build()
+    ///     .unwrap();
+    /// let text = "モーニング娘。と#Hash。です。";
+    /// let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+    /// let expected = vec!["モーニング娘。と#Hash。です。"];
+    /// assert_eq!(sentences, expected);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// An error will arise when the reader fails or the input is not valid UTF-8.
+    pub fn no_break_words_from_reader<R>(mut self, reader: R) -> Result<Self>
+    where
+        R: BufRead,
+    {
+        for line in reader.lines() {
+            let line = line?;
+            if line.starts_with('#') || line.trim().is_empty() {
+                continue;
+            }
+            let word = match line.strip_prefix('\\') {
+                Some(rest) if rest.starts_with('#') => rest.to_string(),
+                _ => line,
+            };
+            self.words
+                .push((word, WordBoundary::Any, WordBoundary::Any));
+        }
+        Ok(self)
+    }
+
+    /// Sets whether no-break words are matched ignoring ASCII case,
+    /// e.g., `Mr.` matches `MR.` and `mr.`. The default value is `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use easy_segmenter::SegmenterBuilder;
+    ///
+    /// let seg = SegmenterBuilder::new()
+    ///     .in_delimiters([". "])
+    ///     .no_break_words(["Mr. "])
+    ///     .word_ascii_case_insensitive(true)
+    ///     .build()
+    ///     .unwrap();
+    /// let text = "I met MR. Smith. He is kind.";
+    /// let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+    /// let expected = vec!["I met MR. Smith. ", "He is kind."];
+    /// assert_eq!(sentences, expected);
+    /// ```
+    pub const fn word_ascii_case_insensitive(mut self, yes: bool) -> Self {
+        self.word_ascii_case_insensitive = yes;
+        self
+    }
+
+    /// Sets the normalization for matching no-break words.
+    /// The default value is [`WordNormalization::None`].
+    ///
+    /// Both the words and input texts are normalized only for matching,
+    /// and resulting positions always refer to the original texts.
+    /// Combined with [`Self::word_ascii_case_insensitive`], a single entry can cover
+    /// the width and case variants of a word.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use easy_segmenter::segmenter::builder::WordNormalization;
+    /// use easy_segmenter::SegmenterBuilder;
+    ///
+    /// let seg = SegmenterBuilder::new()
+    ///     .in_delimiters(["．", "。"])
+    ///     .no_break_words(["Mr."])
+    ///     .word_ascii_case_insensitive(true)
+    ///     .word_normalization(WordNormalization::FoldWidth)
+    ///     .build()
+    ///     .unwrap();
+    /// let text = "ＭＲ．田中に会った。";
+    /// let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+    /// let expected = vec!["ＭＲ．田中に会った。"];
+    /// assert_eq!(sentences, expected);
+    /// ```
+    pub const fn word_normalization(mut self, normalization: WordNormalization) -> Self {
+        self.word_normalization = normalization;
+        self
+    }
+
+    /// Sets the automaton to match no-break words.
+    /// The default value is [`WordMatcherKind::Auto`].
+    ///
+    /// [`WordMatcherKind::Nfa`] is recommended to reduce memory usage
+    /// for dictionaries with tens of thousands of words.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use easy_segmenter::segmenter::builder::WordMatcherKind;
+    /// use easy_segmenter::SegmenterBuilder;
+    ///
+    /// let seg = SegmenterBuilder::new()
+    ///     .in_delimiters(["。"])
+    ///     .no_break_words(["モーニング娘。"])
+    ///     .word_matcher_kind(WordMatcherKind::Nfa)
+    ///     .build()
+    ///     .unwrap();
+    /// let text = "モーニング娘。の新曲";
+    /// let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+    /// let expected = vec!["モーニング娘。の新曲"];
+    /// assert_eq!(sentences, expected);
+    /// ```
+    pub const fn word_matcher_kind(mut self, kind: WordMatcherKind) -> Self {
+        self.word_matcher_kind = kind;
+        self
+    }
+
+    /// Sets the characters trimmed from both ends of each sentence.
+    /// The default value is [`TrimPolicy::None`].
+    ///
+    /// Only the resulting ranges are adjusted, and the positions of sentence boundaries
+    /// are not affected. Sentences consisting only of the trimmed characters become empty,
+    /// which can be dropped through [`Self::drop_empty_sentences`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use easy_segmenter::segmenter::builder::TrimPolicy;
+    /// use easy_segmenter::SegmenterBuilder;
+    ///
+    /// let seg = SegmenterBuilder::new()
+    ///     .in_delimiters(["。"])
+    ///     .ex_delimiters(["\n"])
+    ///     .trim_policy(TrimPolicy::Unicode)
+    ///     .build()
+    ///     .unwrap();
+    /// let text = "　吾輩は猫である。 名前はまだ無い \n";
+    /// let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+    /// let expected = vec!["吾輩は猫である。", "名前はまだ無い"];
+    /// assert_eq!(sentences, expected);
+    /// ```
+    pub fn trim_policy(mut self, policy: TrimPolicy) -> Self {
+        self.trim_policy = policy;
+        self
+    }
+
+    /// Sets whether sentences that are empty after trimming are dropped.
+    /// The default value is `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use easy_segmenter::segmenter::builder::TrimPolicy;
+    /// use easy_segmenter::SegmenterBuilder;
+    ///
+    /// let seg = SegmenterBuilder::new()
+    ///     .ex_delimiters(["\n"])
+    ///     .trim_policy(TrimPolicy::Class("[ 　]".to_string()))
+    ///     .drop_empty_sentences(true)
+    ///     .build()
+    ///     .unwrap();
+    /// let text = "はい\n　 \nいいえ";
+    /// let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+    /// let expected = vec!["はい", "いいえ"];
+    /// assert_eq!(sentences, expected);
+    /// ```
+    pub const fn drop_empty_sentences(mut self, yes: bool) -> Self {
+        self.drop_empty_sentences = yes;
+        self
+    }
+
+    /// Adds regex patterns that should not be broken.
+    /// Captured patterns will not be broken.
+    ///
+    /// Regular expressions are powerful, but complicated ones can slow down segmentation.
+    /// Consider using [`Self::no_break_words`] first to solve your problem.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use regex::Regex;
+    /// use easy_segmenter::SegmenterBuilder;
+    ///
+    /// let seg = SegmenterBuilder::new()
+    ///     .in_delimiters(["．"])
+    ///     .no_break_regex(Regex::new(r"\d(．)\d").unwrap())
+    ///     .build()
+    ///     .unwrap();
+    /// let text = "３．１４";
+    /// let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+    /// let expected = vec!["３．１４"];
+    /// assert_eq!(sentences, expected);
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// This function takes a single regex pattern, not a sequence of those, because
+    ///  - many regex patterns should be registered as a general rule, and
+    ///  - a single regex can define multiple rules.
+    ///
+    /// Nonetheless, you can register multiple patterns by repeating this function.
+    pub fn no_break_regex(mut self, regex: Regex) -> Self {
+        self.regexes.push((None, regex));
+        self
+    }
+
+    /// Adds words after which texts are always broken,
+    /// even inside quotations and no-break words.
+    ///
+    /// Unlike delimiters, forced breaks take precedence over all the no-break rules.
+    /// The resulting sentences have no [`Delimiter`](crate::segmenter::Delimiter),
+    /// and [`Sentence::parent_quote`](crate::Sentence::parent_quote) does not report
+    /// quotations broken by them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use easy_segmenter::SegmenterBuilder;
+    ///
+    /// let seg = SegmenterBuilder::new()
+    ///     .in_delimiters(["。"])
+    ///     .quotes([('「', '」')])
+    ///     .force_break_words(["\n\n"])
+    ///     .build()
+    ///     .unwrap();
+    /// let text = "「はい。\n\nいいえ。」";
+    /// let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+    /// let expected = vec!["「はい。\n\n", "いいえ。」"];
+    /// assert_eq!(sentences, expected);
+    /// ```
+    pub fn force_break_words<I, P>(mut self, words: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<str>,
+    {
+        words
+            .into_iter()
+            .map(|w| w.as_ref().to_string())
+            .for_each(|w| self.force_break_words.push(w));
+        self
+    }
+
+    /// Adds a regex pattern that always breaks texts, even inside quotations
+    /// and no-break words. Texts are broken at the start of each captured group,
+    /// or at the end of the match if the pattern has no group.
+    ///
+    /// See [`Self::force_break_words`] for the precedence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use regex::Regex;
+    /// use easy_segmenter::SegmenterBuilder;
+    ///
+    /// let seg = SegmenterBuilder::new()
+    ///     .in_delimiters(["。"])
+    ///     .quotes([('「', '」')])
+    ///     .force_break_regex(Regex::new(r"\n(第\d+条)").unwrap())
+    ///     .build()
+    ///     .unwrap();
+    /// let text = "「前文\n第1条　目的。\n第2条　定義。」";
+    /// let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+    /// let expected = vec!["「前文\n", "第1条　目的。\n", "第2条　定義。」"];
+    /// assert_eq!(sentences, expected);
+    /// ```
+    pub fn force_break_regex(mut self, regex: Regex) -> Self {
+        self.force_break_regexes.push((None, regex));
+        self
+    }
+
+    /// Sets the maximum nested level of quotations.
+    /// The default value is [`DEFAULT_MAX_QUOTE_LEVEL`].
+    ///
+    /// A smaller value will speed up segmentation but
+    /// make it more susceptible to errant pairs of quotation marks.
+    ///
+    /// # Errors
+    ///
+    /// An error will arise when `max_quote_level == 0`.
+    pub fn max_quote_level(mut self, max_quote_level: usize) -> Result<Self> {
+        if max_quote_level == 0 {
+            Err(EasySegmenterError::input(
+                "max_quote_level must not be zero.",
+            ))
+        } else {
+            self.max_quote_level = max_quote_level;
+            Ok(self)
+        }
+    }
+
+    /// Sets the maximum length of quotations in characters, including the marks.
+    /// An opening mark is discarded if the quotation is not closed within the length,
+    /// which prevents a missing closing mark from swallowing the following paragraphs.
+    /// There is no limit by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use easy_segmenter::SegmenterBuilder;
+    ///
+    /// let seg = SegmenterBuilder::new()
+    ///     .in_delimiters(["。"])
+    ///     .quotes([('「', '」')])
+    ///     .max_quote_len(8)
+    ///     .unwrap()
+    ///     .build()
+    ///     .unwrap();
+    /// let text = "「はい。」「これは。長すぎる。」";
+    /// let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+    /// let expected = vec!["「はい。」「これは。", "長すぎる。", "」"];
+    /// assert_eq!(sentences, expected);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// An error will arise when `max_quote_len == 0`.
+    pub fn max_quote_len(mut self, max_quote_len: usize) -> Result<Self> {
+        if max_quote_len == 0 {
+            Err(EasySegmenterError::input("max_quote_len must not be zero."))
+        } else {
+            self.max_quote_len = Some(max_quote_len);
+            Ok(self)
+        }
+    }
+
+    /// Adds delimiters at which all open quotations are discarded, such as blank lines.
+    ///
+    /// These delimiters do not break texts by themselves,
+    /// so they should also be added through [`Self::ex_delimiters`] or [`Self::in_delimiters`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use easy_segmenter::SegmenterBuilder;
+    ///
+    /// let seg = SegmenterBuilder::new()
+    ///     .in_delimiters(["。"])
+    ///     .ex_delimiters(["\n"])
+    ///     .quotes([('「', '」')])
+    ///     .quote_reset_delimiters(["\n\n"])
+    ///     .build()
+    ///     .unwrap();
+    /// let text = "「はい。\n\nいいえ。」";
+    /// let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+    /// let expected = vec!["「はい。", "いいえ。", "」"];
+    /// assert_eq!(sentences, expected);
+    /// ```
+    pub fn quote_reset_delimiters<I, P>(mut self, delimiters: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<str>,
+    {
+        delimiters
+            .into_iter()
+            .map(|p| p.as_ref().to_string())
+            .for_each(|p| self.quote_reset_delimiters.push(p));
+        self
+    }
+
+    /// Sets the policy for a closing quotation mark that does not correspond to
+    /// the innermost open quotation. The default value is [`QuoteMismatchPolicy::Skip`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use easy_segmenter::segmenter::builder::QuoteMismatchPolicy;
+    /// use easy_segmenter::SegmenterBuilder;
+    ///
+    /// let seg = SegmenterBuilder::new()
+    ///     .in_delimiters(["。"])
+    ///     .quotes([('「', '」'), ('（', '）')])
+    ///     .quote_mismatch_policy(QuoteMismatchPolicy::PopToMatch)
+    ///     .build()
+    ///     .unwrap();
+    /// let text = "「はい（そう。」次。";
+    /// let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+    /// let expected = vec!["「はい（そう。」次。"];
+    /// assert_eq!(sentences, expected);
+    /// ```
+    pub const fn quote_mismatch_policy(mut self, policy: QuoteMismatchPolicy) -> Self {
+        self.quote_mismatch_policy = policy;
+        self
+    }
+}
+
+/// Compiles regex patterns in a table of rules, keeping their names.
+fn compile_regex_table(
+    table_name: &str,
+    table: &BTreeMap<String, String>,
+) -> Result<Vec<(Option<String>, Regex)>> {
+    table
+        .iter()
+        .map(|(name, pattern)| {
+            let regex = Regex::new(pattern).map_err(|e| {
+                EasySegmenterError::input(format!(
+                    "Invalid rule in {table_name}.{name} = {pattern:?}: {e}"
+                ))
+            })?;
+            Ok((Some(name.clone()), regex))
+        })
+        .collect()
+}
+
+/// Exports regex patterns into a table keyed by their names,
+/// where unnamed patterns are named `regex0`, `regex1`, and so on.
+/// The indices are zero-padded to the same width, so that the names sort
+/// in the order of registration.
+fn regex_table(regexes: &[(Option<String>, Regex)]) -> BTreeMap<String, String> {
+    let mut table = BTreeMap::new();
+    for (name, re) in regexes {
+        if let Some(name) = name {
+            table.insert(name.clone(), re.as_str().to_string());
+        }
+    }
+    // Indices are less than the number of patterns even if some names are taken.
+    let width = regexes.len().saturating_sub(1).to_string().len();
+    let mut idx = 0;
+    for (name, re) in regexes {
+        if name.is_some() {
+            continue;
+        }
+        while table.contains_key(&format!("regex{idx:0width$}")) {
+            idx += 1;
+        }
+        table.insert(format!("regex{idx:0width$}"), re.as_str().to_string());
+    }
+    table
+}
+
+impl Default for SegmenterBuilder {
+    fn default() -> Self {
+        Self::new()
     }
 }
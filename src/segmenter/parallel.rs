@@ -0,0 +1,213 @@
+//! Parallel segmentation, which is enabled by the `rayon` feature.
+use std::ops::Range;
+
+use rayon::prelude::*;
+
+use crate::segmenter::{Detection, Segmenter};
+
+/// Minimum length of chunks segmented in parallel.
+const MIN_CHUNK_LEN: usize = 1 << 16;
+
+/// Chunk of a text segmented independently.
+struct Chunk {
+    range: Range<usize>,
+    state: ChunkState,
+    // Number of times the chunk has been merged with the following chunks.
+    num_merges: u32,
+}
+
+enum ChunkState {
+    Pending,
+    Segmented(Vec<(usize, usize)>),
+    // The end of the chunk turned out not to be a sentence boundary that can be cut.
+    Invalid,
+}
+
+impl Segmenter {
+    /// Segments an input text into sentences in parallel, returning byte-position ranges.
+    ///
+    /// The results are the same as [`Self::segment`].
+    /// The text is split into chunks at delimiters, preferably exclusive ones such as line breaks,
+    /// and the chunks are segmented concurrently.
+    /// If a chunk turns out to end inside a quotation or a pattern match,
+    /// it is merged with the following chunks and segmented again.
+    /// Short texts are segmented serially.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use easy_segmenter::template::ja;
+    ///
+    /// let seg = ja::general().build().unwrap();
+    /// let text = "「はい。そうです。」\n".repeat(10000);
+    /// let expected: Vec<_> = seg.segment(&text).collect();
+    /// assert_eq!(seg.segment_par(&text), expected);
+    /// ```
+    pub fn segment_par(&self, text: &str) -> Vec<(usize, usize)> {
+        let mut chunks: Vec<_> = self
+            .split_chunks(text)
+            .into_iter()
+            .map(|range| Chunk {
+                range,
+                state: ChunkState::Pending,
+                num_merges: 0,
+            })
+            .collect();
+        loop {
+            chunks.par_iter_mut().for_each(|chunk| {
+                if matches!(chunk.state, ChunkState::Pending) {
+                    chunk.state = self.segment_chunk(text, chunk.range.clone());
+                }
+            });
+            if chunks
+                .iter()
+                .all(|chunk| matches!(chunk.state, ChunkState::Segmented(_)))
+            {
+                break;
+            }
+            // Merges each invalid chunk with the following chunks,
+            // doubling the number of merged chunks on every retry.
+            let mut merged = Vec::with_capacity(chunks.len());
+            let mut iter = chunks.into_iter();
+            while let Some(chunk) = iter.next() {
+                if !matches!(chunk.state, ChunkState::Invalid) {
+                    merged.push(chunk);
+                    continue;
+                }
+                let end = iter
+                    .by_ref()
+                    .take(1 << chunk.num_merges)
+                    .last()
+                    .map_or(chunk.range.end, |c| c.range.end);
+                merged.push(Chunk {
+                    range: chunk.range.start..end,
+                    state: ChunkState::Pending,
+                    num_merges: chunk.num_merges + 1,
+                });
+            }
+            chunks = merged;
+        }
+        chunks
+            .into_iter()
+            .flat_map(|chunk| match chunk.state {
+                ChunkState::Segmented(spans) => spans,
+                _ => unreachable!(),
+            })
+            .collect()
+    }
+
+    /// Splits a text into chunks at positions that are likely to be cut.
+    fn split_chunks(&self, text: &str) -> Vec<Range<usize>> {
+        let chunk_len = (text.len() / (rayon::current_num_threads() * 4)).max(MIN_CHUNK_LEN);
+        let mut chunks = vec![];
+        let mut start = 0;
+        while let Some(end) = self.find_split_point(text, start + chunk_len, chunk_len) {
+            chunks.push(start..end);
+            start = end;
+        }
+        chunks.push(start..text.len());
+        chunks
+    }
+
+    /// Finds the end of a delimiter in `text[pos..pos + window]`, including trailing attachers,
+    /// that is not crossed by any pattern, preferring exclusive delimiters since they are
+    /// often line breaks.
+    fn find_split_point(&self, text: &str, pos: usize, window: usize) -> Option<usize> {
+        if pos >= text.len() {
+            return None;
+        }
+        let pos = (pos..text.len()).find(|&i| text.is_char_boundary(i))?;
+        let mut first = None;
+        for m in self.delimiter_matcher.iter(text, pos..text.len()) {
+            let end = if m.is_in_delimiter {
+                self.delimiter_matcher
+                    .attach_trailing(text, m.end, text.len())
+            } else {
+                m.end
+            };
+            if m.start >= pos + window || end >= text.len() {
+                break;
+            }
+            if self.occurs_across(text, end)
+                || (self.delimiter_matcher.is_mergeable(text, &m) && self.merges_at(text, end))
+            {
+                continue;
+            }
+            if !m.is_in_delimiter {
+                return Some(end);
+            }
+            first = first.or(Some(end));
+        }
+        first
+    }
+
+    /// Checks if a mergeable delimiter starts at `pos`,
+    /// which can be merged with the delimiter ending at `pos`.
+    fn merges_at(&self, text: &str, pos: usize) -> bool {
+        self.delimiter_matcher
+            .iter(text, pos..text.len())
+            .next()
+            .is_some_and(|m| m.start == pos && self.delimiter_matcher.is_mergeable(text, &m))
+    }
+
+    /// Checks if a delimiter, quotation mark, word, or force-break word
+    /// can be matched across `pos`.
+    fn occurs_across(&self, text: &str, pos: usize) -> bool {
+        self.delimiter_matcher.occurs_across(text, pos)
+            || self
+                .quote_matcher
+                .as_ref()
+                .is_some_and(|m| m.occurs_across(text, pos))
+            || self
+                .word_matcher
+                .as_ref()
+                .is_some_and(|m| m.occurs_across(text, pos))
+            || self
+                .force_break_matcher
+                .as_ref()
+                .is_some_and(|m| m.occurs_across(text, pos))
+    }
+
+    /// Segments `text[range]`, checking that the chunk can be cut at the end.
+    ///
+    /// A chunk segmented independently gives the same results as the entire text if
+    ///  - no pattern can be matched across the boundaries of the chunk,
+    ///  - all quotations in the chunk are closed, no regex match crosses the end,
+    ///    and the last character is not protected,
+    ///  - no regex delimiter or trailing attacher in the chunk ends after the chunk,
+    ///  - the last delimiter is not merged with the following ones, and
+    ///  - the chunk ends with a sentence boundary.
+    ///
+    /// The first condition is ensured by [`Self::find_split_point`].
+    fn segment_chunk(&self, text: &str, range: Range<usize>) -> ChunkState {
+        let is_last = range.end == text.len();
+        let mut detection = Detection::new(range.clone(), !is_last);
+        self.detect(text, range.clone(), &mut detection);
+        if !is_last && !detection.is_cuttable(range.end) {
+            return ChunkState::Invalid;
+        }
+        let mut spans = vec![];
+        for span in self.spans_with(text, range.clone(), &detection) {
+            // The last sentence without a delimiter may continue after the chunk.
+            if !is_last && span.delimiter.is_none() && span.end == range.end {
+                return ChunkState::Invalid;
+            }
+            if span.delimiter.is_some_and(|m| m.end > range.end) {
+                return ChunkState::Invalid;
+            }
+            // The last delimiter may be merged with the following ones.
+            if !is_last
+                && span
+                    .delimiter
+                    .is_some_and(|m| m.is_in_delimiter && m.end == range.end)
+                && self.merges_at(text, range.end)
+            {
+                return ChunkState::Invalid;
+            }
+            if let Some(span) = self.trim(text, span) {
+                spans.push((span.start, span.end));
+            }
+        }
+        ChunkState::Segmented(spans)
+    }
+}
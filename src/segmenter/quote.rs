@@ -0,0 +1,102 @@
+//! Quotation structure detected by [`Segmenter`].
+use std::ops::Range;
+
+use crate::segmenter::{Detection, Segmenter};
+
+/// Quotation or unmatched quotation mark in an input text,
+/// which is returned by [`Segmenter::quotes`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct QuoteSpan {
+    byte_range: Range<usize>,
+    pair_id: usize,
+    depth: usize,
+    is_matched: bool,
+}
+
+impl QuoteSpan {
+    pub(crate) const fn new(byte_range: Range<usize>, pair_id: usize, is_matched: bool) -> Self {
+        Self {
+            byte_range,
+            pair_id,
+            depth: 0,
+            is_matched,
+        }
+    }
+
+    /// Returns the byte-position range of the quotation including the marks,
+    /// or of the mark itself if it is unmatched.
+    pub fn byte_range(&self) -> Range<usize> {
+        self.byte_range.clone()
+    }
+
+    /// Returns the index of the quotation pair in the order of registration,
+    /// where pairs in `quotes` precede those in `quote_pairs` in rule files.
+    pub const fn pair_id(&self) -> usize {
+        self.pair_id
+    }
+
+    /// Returns the number of matched quotations enclosing this one, starting from zero.
+    pub const fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Checks if the opening mark is closed by the corresponding mark.
+    /// Otherwise, the span is an opening mark that is never closed or discarded,
+    /// or a closing mark that does not close any quotation.
+    pub const fn is_matched(&self) -> bool {
+        self.is_matched
+    }
+}
+
+impl Segmenter {
+    /// Detects quotations in an input text in the same manner as segmentation,
+    /// returning them in the order of the start positions.
+    ///
+    /// Unmatched marks are also returned so that broken brackets can be inspected.
+    /// Since quotations are returned in order, they can be associated with sentences
+    /// by comparing byte positions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use easy_segmenter::SegmenterBuilder;
+    ///
+    /// let seg = SegmenterBuilder::new()
+    ///     .in_delimiters(["。"])
+    ///     .quotes([('「', '」'), ('（', '）')])
+    ///     .build()
+    ///     .unwrap();
+    /// let text = "「はい（笑）」と。）";
+    /// let quotes: Vec<_> = seg
+    ///     .quotes(text)
+    ///     .into_iter()
+    ///     .map(|q| (&text[q.byte_range()], q.pair_id(), q.depth(), q.is_matched()))
+    ///     .collect();
+    /// let expected = vec![
+    ///     ("「はい（笑）」", 0, 0, true),
+    ///     ("（笑）", 1, 1, true),
+    ///     ("）", 1, 0, false),
+    /// ];
+    /// assert_eq!(quotes, expected);
+    /// ```
+    pub fn quotes(&self, text: &str) -> Vec<QuoteSpan> {
+        let mut detection = Detection::new(0..text.len(), false);
+        detection.quote_spans = Some(vec![]);
+        self.find_quotes(text, 0..text.len(), &mut detection);
+        let mut spans = detection.quote_spans.unwrap();
+        // Outer quotations precede inner ones starting at the same position.
+        spans.sort_unstable_by_key(|q| (q.byte_range.start, std::cmp::Reverse(q.byte_range.end)));
+        // Matched quotations are nested properly since they are closed in the LIFO order.
+        let mut ends: Vec<usize> = vec![];
+        for q in &mut spans {
+            while ends.last().is_some_and(|&end| end <= q.byte_range.start) {
+                ends.pop();
+            }
+            q.depth = ends.len();
+            if q.is_matched {
+                ends.push(q.byte_range.end);
+            }
+        }
+        spans
+    }
+}
@@ -0,0 +1,225 @@
+//! Sentences returned by [`Segmenter`](crate::Segmenter).
+use std::ops::Range;
+
+use crate::segmenter::Span;
+
+/// Sentence in an input text.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Sentence<'a> {
+    text: &'a str,
+    byte_range: Range<usize>,
+    char_range: Range<usize>,
+    delimiter: Option<Delimiter<'a>>,
+    parent_quote: Option<Range<usize>>,
+    index: usize,
+}
+
+impl<'a> Sentence<'a> {
+    pub(crate) fn new(text: &'a str, span: Span, char_range: Range<usize>, index: usize) -> Self {
+        Self {
+            text,
+            byte_range: span.start..span.end,
+            char_range,
+            delimiter: span.delimiter.map(|m| Delimiter {
+                text,
+                byte_range: m.start..m.end,
+                is_inclusive: m.is_in_delimiter,
+            }),
+            parent_quote: span.parent_quote,
+            index,
+        }
+    }
+
+    /// Returns the sentence string.
+    pub fn as_str(&self) -> &'a str {
+        &self.text[self.byte_range.clone()]
+    }
+
+    /// Returns the byte-position range of the sentence in the input text.
+    pub fn byte_range(&self) -> Range<usize> {
+        self.byte_range.clone()
+    }
+
+    /// Returns the character-position range of the sentence in the input text.
+    pub fn char_range(&self) -> Range<usize> {
+        self.char_range.clone()
+    }
+
+    /// Returns the delimiter that ends the sentence,
+    /// or `None` if the sentence is terminated by the end of the input text.
+    ///
+    /// An inclusive delimiter covers the run merged by
+    /// [`SegmenterBuilder::mergeable_delimiters`](crate::SegmenterBuilder::mergeable_delimiters)
+    /// and the closers attached by
+    /// [`SegmenterBuilder::trailing_attachers`](crate::SegmenterBuilder::trailing_attachers).
+    /// It is clipped to the sentence trimmed by
+    /// [`SegmenterBuilder::trim_policy`](crate::SegmenterBuilder::trim_policy),
+    /// so it can be empty if the trimmed characters are delimiters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use easy_segmenter::SegmenterBuilder;
+    ///
+    /// let seg = SegmenterBuilder::new()
+    ///     .in_delimiters(["。", "！"])
+    ///     .mergeable_delimiters(["！"])
+    ///     .trailing_attachers(["」"])
+    ///     .build()
+    ///     .unwrap();
+    /// let text = "「はい。」本当！！";
+    /// let delimiters: Vec<_> = seg
+    ///     .sentences(text)
+    ///     .map(|s| s.delimiter().unwrap().as_str())
+    ///     .collect();
+    /// assert_eq!(delimiters, ["。」", "！！"]);
+    /// ```
+    pub const fn delimiter(&self) -> Option<&Delimiter<'a>> {
+        self.delimiter.as_ref()
+    }
+
+    /// Returns the byte-position range of the quotation, including the marks,
+    /// that is broken into multiple sentences including this sentence,
+    /// or `None` if the sentence is not a part of such a quotation.
+    ///
+    /// Quotations are broken only if allowed by
+    /// [`SegmenterBuilder::quote_policy`](crate::SegmenterBuilder::quote_policy).
+    /// If nested quotations are broken, the outermost one is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use easy_segmenter::segmenter::builder::QuotePolicy;
+    /// use easy_segmenter::SegmenterBuilder;
+    ///
+    /// let seg = SegmenterBuilder::new()
+    ///     .in_delimiters(["。"])
+    ///     .quotes([('「', '」')])
+    ///     .quote_policy('「', QuotePolicy::SplitInside)
+    ///     .build()
+    ///     .unwrap();
+    /// let text = "はい。「そう。です。」と。";
+    /// let sentences: Vec<_> = seg.sentences(text).collect();
+    /// assert_eq!(sentences[0].parent_quote(), None);
+    /// assert_eq!(sentences[1].as_str(), "「そう。");
+    /// assert_eq!(sentences[1].parent_quote(), Some(9..33));
+    /// assert_eq!(sentences[2].as_str(), "です。」と。");
+    /// assert_eq!(sentences[2].parent_quote(), Some(9..33));
+    /// ```
+    pub fn parent_quote(&self) -> Option<Range<usize>> {
+        self.parent_quote.clone()
+    }
+
+    /// Returns the index of the sentence in the input text, starting from zero.
+    pub const fn index(&self) -> usize {
+        self.index
+    }
+}
+
+/// Delimiter that ends a [`Sentence`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Delimiter<'a> {
+    text: &'a str,
+    byte_range: Range<usize>,
+    is_inclusive: bool,
+}
+
+impl<'a> Delimiter<'a> {
+    /// Returns the delimiter string.
+    pub fn as_str(&self) -> &'a str {
+        &self.text[self.byte_range.clone()]
+    }
+
+    /// Returns the byte-position range of the delimiter in the input text.
+    pub fn byte_range(&self) -> Range<usize> {
+        self.byte_range.clone()
+    }
+
+    /// Checks if the delimiter is inclusive, i.e., included in the sentence.
+    /// Otherwise, the delimiter is exclusive and follows the sentence.
+    pub const fn is_inclusive(&self) -> bool {
+        self.is_inclusive
+    }
+}
+
+/// Sentence that owns its string, which is returned by streaming segmentation.
+///
+/// The positions are offsets from the beginning of the stream.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OwnedSentence {
+    text: String,
+    byte_range: Range<usize>,
+    char_range: Range<usize>,
+    parent_quote: Option<Range<usize>>,
+    index: usize,
+}
+
+impl OwnedSentence {
+    pub(crate) const fn new(
+        text: String,
+        byte_range: Range<usize>,
+        char_range: Range<usize>,
+        parent_quote: Option<Range<usize>>,
+        index: usize,
+    ) -> Self {
+        Self {
+            text,
+            byte_range,
+            char_range,
+            parent_quote,
+            index,
+        }
+    }
+
+    /// Returns the sentence string.
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// Returns the sentence string, consuming the sentence.
+    pub fn into_string(self) -> String {
+        self.text
+    }
+
+    /// Returns the byte-position range of the sentence in the stream.
+    pub fn byte_range(&self) -> Range<usize> {
+        self.byte_range.clone()
+    }
+
+    /// Returns the character-position range of the sentence in the stream.
+    pub fn char_range(&self) -> Range<usize> {
+        self.char_range.clone()
+    }
+
+    /// Returns the byte-position range of the quotation in the stream
+    /// that is broken into multiple sentences including this sentence.
+    /// See [`Sentence::parent_quote`] for the details.
+    pub fn parent_quote(&self) -> Option<Range<usize>> {
+        self.parent_quote.clone()
+    }
+
+    /// Returns the index of the sentence in the stream, starting from zero.
+    pub const fn index(&self) -> usize {
+        self.index
+    }
+}
+
+/// Cursor to convert increasing byte positions into character positions.
+#[derive(Default)]
+pub(crate) struct CharCursor {
+    byte_pos: usize,
+    char_pos: usize,
+}
+
+impl CharCursor {
+    pub const fn new(byte_pos: usize, char_pos: usize) -> Self {
+        Self { byte_pos, char_pos }
+    }
+
+    /// Moves the cursor forward to `byte_pos` and returns the character position.
+    pub fn advance(&mut self, text: &str, byte_pos: usize) -> usize {
+        self.char_pos += text[self.byte_pos..byte_pos].chars().count();
+        self.byte_pos = byte_pos;
+        self.char_pos
+    }
+}
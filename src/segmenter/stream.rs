@@ -0,0 +1,763 @@
+//! Streaming segmentation.
+use std::collections::VecDeque;
+use std::io::{self, BufRead};
+
+use std::ops::Range;
+
+use regex::Regex;
+use regex_automata::hybrid::dfa::{Cache, DFA};
+use regex_automata::hybrid::LazyStateID;
+use regex_automata::{Anchored, Input};
+
+use crate::segmenter::sentence::{CharCursor, OwnedSentence};
+use crate::segmenter::{Detection, OpenQuote, QuoteSink, Segmenter, Span};
+
+/// Iterator over sentences read from a [`BufRead`],
+/// which is created by [`Segmenter::segment_reader`].
+pub struct SentenceReader<'a, R> {
+    stream: StreamState<'a>,
+    reader: R,
+    // Trailing bytes of an incomplete UTF-8 character.
+    incomplete: Vec<u8>,
+    pending: VecDeque<OwnedSentence>,
+    done: bool,
+}
+
+impl<'a, R> SentenceReader<'a, R>
+where
+    R: BufRead,
+{
+    pub(crate) fn new(segmenter: &'a Segmenter, reader: R) -> Self {
+        let mut stream = StreamState::new(segmenter);
+        stream.max_held_quote_len = DEFAULT_MAX_HELD_QUOTE_LEN;
+        Self {
+            stream,
+            reader,
+            incomplete: vec![],
+            pending: VecDeque::new(),
+            done: false,
+        }
+    }
+
+    /// Sets the maximum length in bytes of an open quotation that holds back the stream.
+    /// The default value is [`DEFAULT_MAX_HELD_QUOTE_LEN`], and `usize::MAX` disables the limit.
+    ///
+    /// A quotation left open longer than this is regarded as unclosed,
+    /// so that a missing closing mark does not hold back the rest of the stream.
+    /// The results differ from [`Segmenter::segment`] only if such a quotation is closed later.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::BufReader;
+    ///
+    /// use easy_segmenter::SegmenterBuilder;
+    ///
+    /// let seg = SegmenterBuilder::new()
+    ///     .in_delimiters(["。"])
+    ///     .quotes([('「', '」')])
+    ///     .build()
+    ///     .unwrap();
+    /// let text = "「はい。そうです。」と答えた。";
+    /// let reader = BufReader::with_capacity(3, text.as_bytes());
+    /// let sentences: Vec<_> = seg
+    ///     .segment_reader(reader)
+    ///     .max_held_quote_len(6)
+    ///     .map(|s| s.unwrap().into_string())
+    ///     .collect();
+    /// let expected = vec!["「はい。", "そうです。", "」と答えた。"];
+    /// assert_eq!(sentences, expected);
+    /// ```
+    pub const fn max_held_quote_len(mut self, len: usize) -> Self {
+        self.stream.max_held_quote_len = len;
+        self
+    }
+
+    fn fill(&mut self) -> io::Result<()> {
+        let buf = match self.reader.fill_buf() {
+            Ok(buf) => buf,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => return Ok(()),
+            Err(e) => return Err(e),
+        };
+        if buf.is_empty() {
+            self.done = true;
+            if !self.incomplete.is_empty() {
+                return Err(invalid_utf8());
+            }
+            self.stream.finish(&mut self.pending);
+            return Ok(());
+        }
+        let len = buf.len();
+        self.incomplete.extend_from_slice(buf);
+        self.reader.consume(len);
+
+        let valid_len = match std::str::from_utf8(&self.incomplete) {
+            Ok(text) => text.len(),
+            Err(e) if e.error_len().is_none() => e.valid_up_to(),
+            Err(_) => return Err(invalid_utf8()),
+        };
+        let text = std::str::from_utf8(&self.incomplete[..valid_len]).unwrap();
+        self.stream.push(text, &mut self.pending);
+        self.incomplete.drain(..valid_len);
+        Ok(())
+    }
+}
+
+impl<'a, R> Iterator for SentenceReader<'a, R>
+where
+    R: BufRead,
+{
+    type Item = io::Result<OwnedSentence>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(sentence) = self.pending.pop_front() {
+                return Some(Ok(sentence));
+            }
+            if self.done {
+                return None;
+            }
+            if let Err(e) = self.fill() {
+                self.done = true;
+                return Some(Err(e));
+            }
+        }
+    }
+}
+
+/// Segmenter that receives a text in pieces, such as chat messages and live transcripts.
+///
+/// The results are the same as [`Segmenter::sentences`] for the concatenated text.
+/// [`Self::push`] returns only sentences that are determined, i.e., that cannot be
+/// changed by subsequent text, and the rest is held back as [`Self::pending_text`].
+/// The nesting of quotations is carried across the pieces, so pushing a piece takes time
+/// proportional to its length in most cases, even while a quotation is open.
+/// Unlike [`Segmenter::segment_reader`], an open quotation holds back the text
+/// however long it is.
+/// See [`Segmenter::segment_reader`] for the conditions to determine sentences.
+///
+/// # Examples
+///
+/// ```
+/// use easy_segmenter::{IncrementalSegmenter, SegmenterBuilder};
+///
+/// let seg = SegmenterBuilder::new()
+///     .in_delimiters(["。"])
+///     .quotes([('「', '」')])
+///     .build()
+///     .unwrap();
+/// let mut inc = IncrementalSegmenter::new(&seg);
+///
+/// let sentences: Vec<_> = inc.push("はい。私は「そう").into_iter().map(|s| s.into_string()).collect();
+/// assert_eq!(sentences, vec!["はい。"]);
+/// assert_eq!(inc.pending_text(), "私は「そう");
+///
+/// let sentences: Vec<_> = inc.push("です。」と答えた。次").into_iter().map(|s| s.into_string()).collect();
+/// assert_eq!(sentences, vec!["私は「そうです。」と答えた。"]);
+///
+/// let sentences: Vec<_> = inc.finish().into_iter().map(|s| s.into_string()).collect();
+/// assert_eq!(sentences, vec!["次"]);
+/// ```
+pub struct IncrementalSegmenter<'a> {
+    state: StreamState<'a>,
+}
+
+impl<'a> IncrementalSegmenter<'a> {
+    /// Creates an instance with the rules of `segmenter`.
+    pub fn new(segmenter: &'a Segmenter) -> Self {
+        Self {
+            state: StreamState::new(segmenter),
+        }
+    }
+
+    /// Appends a piece of text, returning sentences that are determined.
+    /// The positions of the sentences are offsets from the beginning of the first piece.
+    pub fn push(&mut self, text: &str) -> Vec<OwnedSentence> {
+        let mut output = VecDeque::new();
+        self.state.push(text, &mut output);
+        output.into()
+    }
+
+    /// Returns the text that has been pushed but not returned as sentences yet.
+    pub fn pending_text(&self) -> &str {
+        &self.state.buf[self.state.from..]
+    }
+
+    /// Returns all the remaining sentences.
+    pub fn finish(mut self) -> Vec<OwnedSentence> {
+        let mut output = VecDeque::new();
+        self.state.finish(&mut output);
+        output.into()
+    }
+}
+
+fn invalid_utf8() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        "stream did not contain valid UTF-8",
+    )
+}
+
+/// Default maximum length in bytes of an open quotation that holds back the text
+/// in [`SentenceReader`].
+pub const DEFAULT_MAX_HELD_QUOTE_LEN: usize = 1 << 20;
+
+/// State of segmentation over a text given in pieces.
+///
+/// The state holds back the text after the last position where the text can be cut
+/// without changing the results, i.e., a sentence boundary that is
+///  - not inside any quotation, word, or regex match, including unclosed quotations,
+///  - not preceded by a character that should not be broken,
+///  - followed by enough text to determine the longest delimiter, quotation mark, and word, and
+///  - not preceded by the start of a regex match that can be extended by subsequent text.
+///
+/// To avoid scanning the held text again whenever a piece is pushed,
+/// quotation marks and regex patterns are scanned only in the new text,
+/// carrying the open quotations and the progress of the regex patterns, and
+/// the held text is segmented again only from the last position where
+/// the sentences and the detected positions before it are determined.
+/// The text is not segmented at all while it cannot be cut anywhere new,
+/// e.g., while a quotation is open.
+pub(crate) struct StreamState<'a> {
+    segmenter: &'a Segmenter,
+    // Scans to check if regex matches can be extended by subsequent text.
+    // `None` if a DFA cannot be built for the regex.
+    regex_scans: Vec<Option<RegexScan>>,
+    buf: String,
+    // Start of the text to be segmented in `buf`.
+    // The preceding text is kept as the context of regex patterns.
+    from: usize,
+    byte_offset: usize,
+    char_offset: usize,
+    num_sentences: usize,
+    // Position from which quotation marks are scanned.
+    quote_pos: usize,
+    quote_stack: Vec<OpenQuote>,
+    // Positions detected by the quotations closed or discarded in the scanned text.
+    quote_events: QuoteEvents,
+    // Position from which the text is segmented again, with the start of the sentence
+    // containing it and whether the preceding character should not be broken.
+    resume_pos: usize,
+    sentence_start: usize,
+    protected_before: bool,
+    // Sentences before `resume_pos` that are determined but cannot be output yet.
+    held_spans: Vec<Span>,
+    // Position up to which the sentence boundaries have been checked.
+    checked: usize,
+    detection: Detection,
+    // Open quotations longer than this are regarded as unclosed,
+    // which is `usize::MAX` unless the held text should be bounded.
+    max_held_quote_len: usize,
+    // Total length of the text scanned for words and patterns.
+    #[cfg(test)]
+    scanned_len: usize,
+}
+
+impl<'a> StreamState<'a> {
+    pub fn new(segmenter: &'a Segmenter) -> Self {
+        let regex_scans = segmenter.regex_matchers().map(RegexScan::new).collect();
+        Self {
+            segmenter,
+            regex_scans,
+            buf: String::new(),
+            from: 0,
+            byte_offset: 0,
+            char_offset: 0,
+            num_sentences: 0,
+            quote_pos: 0,
+            quote_stack: vec![],
+            quote_events: QuoteEvents::default(),
+            resume_pos: 0,
+            sentence_start: 0,
+            protected_before: false,
+            held_spans: vec![],
+            checked: 0,
+            detection: Detection::default(),
+            max_held_quote_len: usize::MAX,
+            #[cfg(test)]
+            scanned_len: 0,
+        }
+    }
+
+    /// Appends a piece of text and outputs sentences that are determined.
+    pub fn push(&mut self, text: &str, output: &mut VecDeque<OwnedSentence>) {
+        self.buf.push_str(text);
+        self.drain(false, output);
+    }
+
+    /// Outputs all the remaining sentences.
+    pub fn finish(&mut self, output: &mut VecDeque<OwnedSentence>) {
+        self.drain(true, output);
+    }
+
+    fn drain(&mut self, finished: bool, output: &mut VecDeque<OwnedSentence>) {
+        let len = self.buf.len();
+        // Patterns ending after `until` can be changed by subsequent text.
+        let until = if finished {
+            len
+        } else {
+            len.saturating_sub(self.segmenter.max_pattern_len())
+        };
+        self.scan_quotes(until, finished);
+        let limit = if finished {
+            len
+        } else {
+            let open_quote = self.quote_stack.first().map_or(len, |q| q.start);
+            until
+                .min(self.settled_regex_pos())
+                .min(self.quote_pos)
+                .min(open_quote)
+        };
+        // No sentence boundary is determined after the last check.
+        if !finished && limit <= self.checked {
+            return;
+        }
+
+        let segmenter = self.segmenter;
+        let text = self.buf.as_str();
+        let resume_pos = self.resume_pos;
+        let context_start = text[..resume_pos]
+            .char_indices()
+            .next_back()
+            .map_or(resume_pos, |(i, _)| i);
+        let detection = &mut self.detection;
+        detection.reset(context_start..len, !finished);
+        if self.protected_before {
+            detection.protect(context_start..resume_pos);
+        }
+        self.quote_events.apply(detection);
+        segmenter.find_words(text, resume_pos..len, detection);
+        segmenter.find_regex(text, resume_pos..len, detection);
+        segmenter.find_forced_breaks(text, resume_pos..len, detection);
+        #[cfg(test)]
+        {
+            self.scanned_len += len - resume_pos;
+        }
+
+        let detection = &self.detection;
+        let mut spans = segmenter.spans_from(text, resume_pos..len, self.sentence_start, detection);
+        let mut new_spans = vec![];
+        // Sentences after the limit can be changed by subsequent text.
+        while finished || spans.next_pos() <= limit {
+            match spans.step() {
+                Some(Some(span)) => new_spans.push(span),
+                Some(None) => {}
+                None => break,
+            }
+        }
+        let sentence_start = spans.start_pos();
+        let resumable_range = spans.resumable_range();
+        drop(spans);
+
+        let cut = if finished {
+            Some((new_spans.len(), len))
+        } else {
+            let mut last = None;
+            for (i, span) in new_spans.iter().enumerate() {
+                // The last sentence without a delimiter may continue in subsequent text,
+                // while the others without delimiters end with forced breaks.
+                let end = match span.delimiter {
+                    Some(m) => m.end,
+                    None if span.end < len => span.end,
+                    None => break,
+                };
+                if end > limit {
+                    break;
+                }
+                if detection.is_cuttable(end) {
+                    last = Some((i + 1, end));
+                }
+            }
+            last
+        };
+
+        if let Some((num_spans, cut)) = cut {
+            let mut cursor = CharCursor::new(self.from, self.char_offset);
+            let spans = self
+                .held_spans
+                .drain(..)
+                .chain(new_spans.drain(..num_spans))
+                .filter_map(|span| segmenter.trim(text, span));
+            for span in spans {
+                let char_start = cursor.advance(text, span.start);
+                let char_end = cursor.advance(text, span.end);
+                output.push_back(OwnedSentence::new(
+                    text[span.start..span.end].to_string(),
+                    self.byte_offset + span.start..self.byte_offset + span.end,
+                    char_start..char_end,
+                    span.parent_quote
+                        .map(|r| self.byte_offset + r.start..self.byte_offset + r.end),
+                    self.num_sentences,
+                ));
+                self.num_sentences += 1;
+            }
+            self.char_offset = cursor.advance(text, cut);
+            self.from = cut;
+        }
+        if finished {
+            return;
+        }
+
+        // Segmentation is resumed from the last position before the limit
+        // where no pattern is matched across, so that the detection can also be resumed.
+        let resume_pos = (resumable_range.start..resumable_range.end.min(limit + 1))
+            .rev()
+            .find(|&pos| {
+                text.is_char_boundary(pos)
+                    && !detection.is_crossed(pos)
+                    && segmenter
+                        .word_matcher
+                        .as_ref()
+                        .is_none_or(|m| m.is_unit_start(text, pos))
+            });
+        if let Some(pos) = resume_pos {
+            self.resume_pos = pos;
+            self.sentence_start = sentence_start;
+            self.protected_before = pos != 0 && detection.is_protected(pos - 1);
+            self.held_spans.append(&mut new_spans);
+        } else if let Some((_, cut)) = cut {
+            // The text can also be segmented from scratch at the cut.
+            self.resume_pos = cut;
+            self.sentence_start = cut;
+            self.protected_before = false;
+        }
+        self.checked = limit;
+        let resume_context = text[..self.resume_pos]
+            .char_indices()
+            .next_back()
+            .map_or(0, |(i, _)| i);
+        self.quote_events
+            .retain_after(self.sentence_start.min(resume_context));
+
+        // Keeps the last character before the cut as the context.
+        let keep = self.from.min(self.quote_pos);
+        let context_start = text[..keep]
+            .char_indices()
+            .next_back()
+            .map_or(0, |(i, _)| i);
+        self.buf.drain(..context_start);
+        self.byte_offset += context_start;
+        self.shift(context_start);
+    }
+
+    /// Scans quotation marks ending by `until` in the new text.
+    fn scan_quotes(&mut self, until: usize, finished: bool) {
+        let text = self.buf.as_str();
+        self.quote_pos = self.segmenter.scan_quotes(
+            text,
+            self.quote_pos..text.len(),
+            until,
+            &mut self.quote_stack,
+            &mut self.quote_events,
+        );
+        if let Some(max_len) = self.segmenter.quote_options.max_len {
+            // Quotations expiring by `until` are discarded regardless of the subsequent marks.
+            let mut num_expired = 0;
+            for q in &mut self.quote_stack {
+                if !q.is_expired(text, max_len, until) {
+                    break;
+                }
+                num_expired += 1;
+            }
+            for q in self.quote_stack.drain(..num_expired) {
+                self.quote_events.cross(q.start..q.expiry.unwrap());
+            }
+            // The expiry not found in the text is searched again in the extended text.
+            for q in &mut self.quote_stack {
+                if q.expiry == Some(usize::MAX) {
+                    q.expiry = None;
+                }
+            }
+        }
+        // An unclosed quotation protects nothing at the end of the text,
+        // which is assumed for too long quotations to bound the held text.
+        if !finished
+            && self
+                .quote_stack
+                .first()
+                .is_some_and(|q| text.len() - q.start > self.max_held_quote_len)
+        {
+            for q in self.quote_stack.drain(..) {
+                self.quote_events.cross(q.start..q.mark_end);
+            }
+        }
+    }
+
+    /// Returns the first position from which a regex match can start and
+    /// be extended by subsequent text.
+    fn settled_regex_pos(&mut self) -> usize {
+        let text = self.buf.as_str();
+        let mut pos = text.len();
+        for scan in &mut self.regex_scans {
+            let Some(scan) = scan.as_mut() else {
+                return self.from;
+            };
+            pos = pos.min(scan.advance(text));
+        }
+        pos
+    }
+
+    /// Moves the positions in the state backward by `len` bytes
+    /// after the text is removed from the buffer.
+    fn shift(&mut self, len: usize) {
+        self.from -= len;
+        self.quote_pos -= len;
+        self.resume_pos -= len;
+        self.sentence_start -= len;
+        self.checked -= len;
+        for q in &mut self.quote_stack {
+            q.start -= len;
+            q.mark_end -= len;
+            if let Some(expiry) = q.expiry.as_mut() {
+                *expiry -= len;
+            }
+        }
+        self.quote_events.shift(len);
+        for span in &mut self.held_spans {
+            span.start -= len;
+            span.end -= len;
+            if let Some(m) = span.delimiter.as_mut() {
+                m.start -= len;
+                m.end -= len;
+            }
+            if let Some(r) = span.parent_quote.as_mut() {
+                *r = r.start.saturating_sub(len)..r.end - len;
+            }
+        }
+        for scan in self.regex_scans.iter_mut().flatten() {
+            scan.shift(len);
+        }
+    }
+}
+
+/// Positions detected by quotations, which are applied to the detection of each segmentation.
+#[derive(Default)]
+struct QuoteEvents {
+    protected: Vec<Range<usize>>,
+    crossed: Vec<Range<usize>>,
+    split_quotes: Vec<Range<usize>>,
+}
+
+impl QuoteEvents {
+    fn apply(&self, detection: &mut Detection) {
+        for r in &self.protected {
+            detection.protect(r.clone());
+        }
+        for r in &self.crossed {
+            detection.cross(r.clone());
+        }
+        detection
+            .split_quotes
+            .extend(self.split_quotes.iter().cloned());
+        detection.split_quotes.sort_unstable_by_key(|r| r.start);
+    }
+
+    /// Removes the positions that do not affect the text after `pos`.
+    fn retain_after(&mut self, pos: usize) {
+        self.protected.retain(|r| r.end > pos);
+        self.crossed.retain(|r| r.end > pos);
+        self.split_quotes.retain(|r| r.end > pos);
+    }
+
+    fn shift(&mut self, len: usize) {
+        for r in self
+            .protected
+            .iter_mut()
+            .chain(&mut self.crossed)
+            .chain(&mut self.split_quotes)
+        {
+            *r = r.start.saturating_sub(len)..r.end - len;
+        }
+    }
+}
+
+impl QuoteSink for QuoteEvents {
+    fn protect(&mut self, r: Range<usize>) {
+        self.protected.push(r);
+    }
+
+    fn cross(&mut self, r: Range<usize>) {
+        self.crossed.push(r);
+    }
+
+    fn split_quote(&mut self, r: Range<usize>) {
+        self.split_quotes.push(r);
+    }
+
+    fn record_quote(&mut self, _: Range<usize>, _: usize, _: bool) {}
+}
+
+/// Anchored searches of a regex pattern from successive positions,
+/// which find the first position from which a match can be extended by subsequent text.
+struct RegexScan {
+    dfa: DFA,
+    cache: Cache,
+    // Start of the current search, before which no match can be extended.
+    start: usize,
+    // State of the current search with the position scanned up to.
+    state: Option<(LazyStateID, usize)>,
+}
+
+impl RegexScan {
+    fn new(re: &Regex) -> Option<Self> {
+        let dfa = DFA::builder()
+            .configure(DFA::config().unicode_word_boundary(true))
+            .build(re.as_str())
+            .ok()?;
+        let cache = dfa.create_cache();
+        Some(Self {
+            dfa,
+            cache,
+            start: 0,
+            state: None,
+        })
+    }
+
+    /// Continues the searches in `text` and returns the start of the search not finished.
+    fn advance(&mut self, text: &str) -> usize {
+        while self.start < text.len() {
+            let (mut sid, pos) = match self.state {
+                Some(state) => state,
+                None => {
+                    let input = Input::new(text).range(self.start..).anchored(Anchored::Yes);
+                    let Ok(sid) = self.dfa.start_state_forward(&mut self.cache, &input) else {
+                        return self.start;
+                    };
+                    (sid, self.start)
+                }
+            };
+            // The search cannot proceed on characters not supported by the DFA.
+            if sid.is_quit() {
+                return self.start;
+            }
+            let mut is_dead = false;
+            for (i, &b) in text.as_bytes()[pos..].iter().enumerate() {
+                sid = match self.dfa.next_state(&mut self.cache, sid, b) {
+                    Ok(sid) => sid,
+                    Err(_) => {
+                        self.state = None;
+                        return self.start;
+                    }
+                };
+                if sid.is_dead() {
+                    is_dead = true;
+                    break;
+                }
+                if sid.is_quit() {
+                    self.state = Some((sid, pos + i + 1));
+                    return self.start;
+                }
+            }
+            if !is_dead {
+                self.state = Some((sid, text.len()));
+                return self.start;
+            }
+            self.state = None;
+            self.start += text[self.start..].chars().next().map_or(1, char::len_utf8);
+        }
+        self.start
+    }
+
+    const fn shift(&mut self, len: usize) {
+        self.start -= len;
+        if let Some((_, pos)) = self.state.as_mut() {
+            *pos -= len;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::template::ja;
+
+    #[test]
+    fn test_buffer_bounded_by_longest_sentence() {
+        let seg = ja::general().build().unwrap();
+        let text = include_str!("../../data/wagahaiwa_nekodearu.txt");
+        let longest = seg.segment(text).map(|(i, j)| j - i).max().unwrap();
+        let mut stream = StreamState::new(&seg);
+        let mut output = VecDeque::new();
+        let mut max_buf_len = 0;
+        let mut pos = 0;
+        while pos < text.len() {
+            let mut end = (pos + 100).min(text.len());
+            while !text.is_char_boundary(end) {
+                end += 1;
+            }
+            stream.push(&text[pos..end], &mut output);
+            max_buf_len = max_buf_len.max(stream.buf.len());
+            pos = end;
+        }
+        stream.finish(&mut output);
+        assert!(
+            max_buf_len <= longest + 200,
+            "{max_buf_len} > {longest} + 200"
+        );
+        assert_eq!(output.len(), seg.segment(text).count());
+    }
+
+    #[test]
+    fn test_buffer_bounded_with_unclosed_quote() {
+        let seg = ja::general().build().unwrap();
+        let text = format!("「{}", include_str!("../../data/wagahaiwa_nekodearu.txt"));
+        let longest = seg.segment(&text[3..]).map(|(i, j)| j - i).max().unwrap();
+        let mut stream = StreamState::new(&seg);
+        stream.max_held_quote_len = 10000;
+        let mut output = VecDeque::new();
+        let mut max_buf_len = 0;
+        let mut pos = 0;
+        while pos < text.len() {
+            let mut end = (pos + 100).min(text.len());
+            while !text.is_char_boundary(end) {
+                end += 1;
+            }
+            stream.push(&text[pos..end], &mut output);
+            max_buf_len = max_buf_len.max(stream.buf.len());
+            pos = end;
+        }
+        stream.finish(&mut output);
+        assert!(
+            max_buf_len <= 10000 + longest + 200,
+            "{max_buf_len} > 10000 + {longest} + 200"
+        );
+        // The quotation is regarded as unclosed, which protects nothing.
+        let sentences: Vec<_> = output.iter().map(|s| s.byte_range()).collect();
+        let expected: Vec<_> = seg
+            .segment(&text.replacen('「', "＊", 1))
+            .map(|(i, j)| i..j)
+            .collect();
+        assert_eq!(sentences, expected);
+    }
+
+    #[test]
+    fn test_push_small_pieces_scans_text_once() {
+        let seg = ja::general().build().unwrap();
+        let text = include_str!("../../data/wagahaiwa_nekodearu.txt");
+        let mut inc = IncrementalSegmenter::new(&seg);
+        let mut sentences = vec![];
+        let mut pos = 0;
+        while pos < text.len() {
+            let mut end = (pos + 16).min(text.len());
+            while !text.is_char_boundary(end) {
+                end += 1;
+            }
+            sentences.extend(
+                inc.push(&text[pos..end])
+                    .into_iter()
+                    .map(|s| s.byte_range()),
+            );
+            pos = end;
+        }
+        let scanned_len = inc.state.scanned_len;
+        sentences.extend(inc.finish().into_iter().map(|s| s.byte_range()));
+        let expected: Vec<_> = seg.segment(text).map(|(i, j)| i..j).collect();
+        assert_eq!(sentences, expected);
+        assert!(
+            scanned_len <= 2 * text.len(),
+            "{scanned_len} > 2 * {}",
+            text.len()
+        );
+    }
+}
@@ -1,5 +1,11 @@
 use super::*;
 
+use crate::errors::EasySegmenterError;
+use crate::segmenter::builder::{
+    QuoteMismatchPolicy, QuotePolicy, SymmetricQuoteRule, TrimPolicy, WordBoundary,
+    WordMatcherKind, WordNormalization,
+};
+
 #[test]
 fn test_simple_1() {
     let seg = SegmenterBuilder::new()
@@ -126,6 +132,292 @@ fn test_quote_4() {
     assert_eq!(sentences, expected);
 }
 
+#[test]
+fn test_quote_multi_char() {
+    let seg = SegmenterBuilder::new()
+        .in_delimiters(["。"])
+        .quotes([("<", ">"), ("<<", ">>"), ("<q>", "</q>")])
+        .build()
+        .unwrap();
+    let text = "私は<<はい。<q>そう。</q>>>と<答えた。>次。";
+    let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+    let expected = vec!["私は<<はい。<q>そう。</q>>>と<答えた。>次。"];
+    assert_eq!(sentences, expected);
+}
+
+#[test]
+fn test_quote_multi_char_max_level() {
+    let seg = SegmenterBuilder::new()
+        .in_delimiters(["。"])
+        .quotes([("【【", "】】"), ("<q>", "</q>")])
+        .max_quote_level(1)
+        .unwrap()
+        .build()
+        .unwrap();
+    let text = "【【はい。<q>そう。</q>】】<q>いいえ。</q>次。";
+    let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+    let expected = vec!["【【はい。<q>そう。</q>】】<q>いいえ。</q>次。"];
+    assert_eq!(sentences, expected);
+
+    let text = "【はい。】";
+    let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+    let expected = vec!["【はい。", "】"];
+    assert_eq!(sentences, expected);
+}
+
+#[test]
+fn test_quote_code_fence() {
+    let seg = SegmenterBuilder::new()
+        .in_delimiters(["\n"])
+        .quotes([("```", "```")])
+        .build()
+        .unwrap();
+    let text = "前\n```\nfn main() {}\n```\n後";
+    let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+    let expected = vec!["前\n", "```\nfn main() {}\n```\n", "後"];
+    assert_eq!(sentences, expected);
+}
+
+#[test]
+fn test_quote_max_len() {
+    let seg = SegmenterBuilder::new()
+        .in_delimiters(["。"])
+        .quotes([('「', '」'), ('（', '）')])
+        .max_quote_len(10)
+        .unwrap()
+        .build()
+        .unwrap();
+    let text = "「はい。（そう。）」「長い。引用の。途中。」次。";
+    let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+    let expected = vec![
+        "「はい。（そう。）」「長い。",
+        "引用の。",
+        "途中。",
+        "」次。",
+    ];
+    assert_eq!(sentences, expected);
+
+    // The inner quotation is still closed after the outer one is discarded.
+    let text = "「長い。引用の。（途中。）」次。";
+    let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+    let expected = vec!["「長い。", "引用の。", "（途中。）」次。"];
+    assert_eq!(sentences, expected);
+}
+
+#[test]
+fn test_quote_reset_delimiters() {
+    let seg = SegmenterBuilder::new()
+        .in_delimiters(["。"])
+        .ex_delimiters(["\n"])
+        .quotes([('「', '」')])
+        .quote_reset_delimiters(["\n\n"])
+        .build()
+        .unwrap();
+    let text = "「はい。\nいいえ。」と。「そう。\n\n次。」終わり。";
+    let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+    let expected = vec!["「はい。\nいいえ。」と。", "「そう。", "次。", "」終わり。"];
+    assert_eq!(sentences, expected);
+}
+
+#[test]
+fn test_quote_mismatch_policy() {
+    let text = "「はい（そう。」次。（いいえ。」）";
+    for (policy, expected) in [
+        (
+            QuoteMismatchPolicy::Skip,
+            vec!["「はい（そう。", "」次。", "（いいえ。」）"],
+        ),
+        (
+            QuoteMismatchPolicy::PopToMatch,
+            vec!["「はい（そう。」次。", "（いいえ。」）"],
+        ),
+        (
+            QuoteMismatchPolicy::Reset,
+            vec!["「はい（そう。", "」次。", "（いいえ。", "」）"],
+        ),
+    ] {
+        let seg = SegmenterBuilder::new()
+            .in_delimiters(["。"])
+            .quotes([('「', '」'), ('（', '）')])
+            .quote_mismatch_policy(policy)
+            .build()
+            .unwrap();
+        let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+        assert_eq!(sentences, expected, "{policy:?}");
+    }
+}
+
+#[test]
+fn test_quote_policy_split_inside() {
+    let seg = SegmenterBuilder::new()
+        .in_delimiters(["。"])
+        .ex_delimiters(["\n"])
+        .quotes([('「', '」'), ('（', '）')])
+        .quote_policy('「', QuotePolicy::SplitInside)
+        .build()
+        .unwrap();
+    let text = "彼は「はい。そう（嘘。本当。）です。」と答えた。「いいえ」\n次。";
+    let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+    let expected = vec![
+        "彼は「はい。",
+        "そう（嘘。本当。）です。」と答えた。",
+        "「いいえ」",
+        "次。",
+    ];
+    assert_eq!(sentences, expected);
+}
+
+#[test]
+fn test_quote_policy_split_if_longer_than() {
+    let seg = SegmenterBuilder::new()
+        .in_delimiters(["。"])
+        .quotes([('「', '」')])
+        .quote_policy('「', QuotePolicy::SplitIfLongerThan(6))
+        .build()
+        .unwrap();
+    let text = "「はい。」「はい。そう。」";
+    let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+    let expected = vec!["「はい。」「はい。", "そう。」"];
+    assert_eq!(sentences, expected);
+}
+
+#[test]
+fn test_quote_policy_undefined() {
+    let result = SegmenterBuilder::new()
+        .in_delimiters(["。"])
+        .quotes([('「', '」')])
+        .quote_policy('（', QuotePolicy::SplitInside)
+        .build();
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_sentences_parent_quote() {
+    let seg = SegmenterBuilder::new()
+        .in_delimiters(["。"])
+        .quotes([('「', '」'), ('『', '』')])
+        .quote_policy('「', QuotePolicy::SplitInside)
+        .quote_policy('『', QuotePolicy::SplitInside)
+        .build()
+        .unwrap();
+    let text = "「あ。『い。う。』え。」お。『か』き。『く。け』";
+    let actual: Vec<_> = seg
+        .sentences(text)
+        .map(|s| (s.as_str(), s.parent_quote().map(|r| &text[r])))
+        .collect();
+    let outer = Some("「あ。『い。う。』え。」");
+    let expected = vec![
+        ("「あ。", outer),
+        ("『い。", outer),
+        ("う。』え。」お。", outer),
+        ("『か』き。", None),
+        ("『く。", Some("『く。け』")),
+        ("け』", Some("『く。け』")),
+    ];
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn test_quotes() {
+    let seg = SegmenterBuilder::new()
+        .in_delimiters(["。"])
+        .quotes([('「', '」'), ('（', '）'), ('"', '"')])
+        .build()
+        .unwrap();
+    let text = "」「あ（い）\"う\"」（え「お";
+    let actual: Vec<_> = seg
+        .quotes(text)
+        .into_iter()
+        .map(|q| {
+            (
+                &text[q.byte_range()],
+                q.pair_id(),
+                q.depth(),
+                q.is_matched(),
+            )
+        })
+        .collect();
+    let expected = vec![
+        ("」", 0, 0, false),
+        ("「あ（い）\"う\"」", 0, 0, true),
+        ("（い）", 1, 1, true),
+        ("\"う\"", 2, 1, true),
+        ("（", 1, 0, false),
+        ("「", 0, 0, false),
+    ];
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn test_quotes_discarded() {
+    let seg = SegmenterBuilder::new()
+        .in_delimiters(["。"])
+        .quotes([('「', '」'), ('（', '）')])
+        .quote_mismatch_policy(QuoteMismatchPolicy::PopToMatch)
+        .max_quote_len(8)
+        .unwrap()
+        .build()
+        .unwrap();
+    let text = "「あ（い」「（う）えおかきくけこ」";
+    let actual: Vec<_> = seg
+        .quotes(text)
+        .into_iter()
+        .map(|q| (&text[q.byte_range()], q.depth(), q.is_matched()))
+        .collect();
+    let expected = vec![
+        ("「あ（い」", 0, true),
+        ("（", 1, false),
+        ("「", 0, false),
+        ("（う）", 0, true),
+        ("」", 0, false),
+    ];
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn test_quote_symmetric_toggle() {
+    let seg = SegmenterBuilder::new()
+        .in_delimiters(["。"])
+        .quotes([('"', '"'), ('「', '」')])
+        .build()
+        .unwrap();
+    let text = r#"彼は"はい。「そう。」"と言った。"いいえ。""#;
+    let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+    let expected = vec![r#"彼は"はい。「そう。」"と言った。"#, r#""いいえ。""#];
+    assert_eq!(sentences, expected);
+}
+
+#[test]
+fn test_quote_symmetric_mismatch() {
+    let seg = SegmenterBuilder::new()
+        .in_delimiters(["。"])
+        .quotes([('"', '"'), ('「', '」')])
+        .build()
+        .unwrap();
+    let text = r#"「あ"い。」う"え。"#;
+    let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+    let expected = vec![r#"「あ"い。」う"え。"#];
+    assert_eq!(sentences, expected);
+}
+
+#[test]
+fn test_quote_symmetric_word_boundary() {
+    let seg = SegmenterBuilder::new()
+        .in_delimiters([". ", "。"])
+        .quotes([('"', '"'), ('\'', '\'')])
+        .symmetric_quote_rule(SymmetricQuoteRule::WordBoundary)
+        .build()
+        .unwrap();
+    let text = "Students' books aren't here. \"Go. Now.\" he said. 彼は\"はい。\"と言った。";
+    let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+    let expected = vec![
+        "Students' books aren't here. ",
+        "\"Go. Now.\" he said. ",
+        "彼は\"はい。\"と言った。",
+    ];
+    assert_eq!(sentences, expected);
+}
+
 #[test]
 fn test_word_1() {
     let seg = SegmenterBuilder::new()
@@ -140,62 +432,1922 @@ fn test_word_1() {
 }
 
 #[test]
-fn test_regex_1() {
+fn test_word_normalization() {
     let seg = SegmenterBuilder::new()
-        .in_delimiters(["．"])
-        .no_break_regex(Regex::new(r"\d(．)\d").unwrap())
+        .in_delimiters(["．", ".", "。"])
+        .no_break_words(["Mr.", "ガ。"])
+        .word_ascii_case_insensitive(true)
+        .word_normalization(WordNormalization::FoldWidth)
         .build()
         .unwrap();
-    let text = "３．１４１５９２．円周率です．";
+    let text = "MR.Smith.ｍｒ．田中．ｶﾞ。ｶ。";
     let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
-    let expected = vec!["３．１４１５９２．", "円周率です．"];
+    let expected = vec!["MR.Smith.", "ｍｒ．田中．", "ｶﾞ。ｶ。"];
     assert_eq!(sentences, expected);
+
+    // Words are matched exactly by default.
+    let seg = SegmenterBuilder::new()
+        .in_delimiters(["．", "."])
+        .no_break_words(["Mr."])
+        .build()
+        .unwrap();
+    let text = "MR.Smith.ｍｒ．田中．";
+    assert_eq!(seg.segment(text).count(), 4);
 }
 
 #[test]
-fn test_regex_2() {
+fn test_word_boundary() {
+    let seg = SegmenterBuilder::new()
+        .in_delimiters([". "])
+        .no_break_words_with_boundaries(
+            ["St. ", "Mr. "],
+            WordBoundary::NonAlphanumeric,
+            WordBoundary::Class("[A-Z]".to_string()),
+        )
+        .build()
+        .unwrap();
+    let text = "He was 1st. Mr. Smith and St. Mary came. Then Mr. he left.";
+    let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+    let expected = vec![
+        "He was 1st. ",
+        "Mr. Smith and St. Mary came. ",
+        "Then Mr. ",
+        "he left.",
+    ];
+    assert_eq!(sentences, expected);
+
+    let result = SegmenterBuilder::new()
+        .in_delimiters([". "])
+        .no_break_words_with_boundaries(
+            ["St. "],
+            WordBoundary::Class("[a-".to_string()),
+            WordBoundary::Any,
+        )
+        .build();
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_force_break_words() {
     let seg = SegmenterBuilder::new()
         .in_delimiters(["。"])
-        .no_break_regex(Regex::new(r"(。{2,})。").unwrap())
+        .ex_delimiters(["\n"])
+        .quotes([('「', '」')])
+        .no_break_words(["娘。\n\n娘"])
+        .force_break_words(["\n\n"])
         .build()
         .unwrap();
-    let text = "はぁ。。。。。。疲れた。。";
+    let text = "「はい。\n\nいいえ。」と娘。\n\n娘。";
+    let sentences: Vec<_> = seg.sentences(text).collect();
+    let actual: Vec<_> = sentences
+        .iter()
+        .map(|s| (s.as_str(), s.delimiter().map(|d| d.as_str())))
+        .collect();
+    let expected = vec![
+        ("「はい。\n\n", None),
+        ("いいえ。」と娘。\n\n", None),
+        ("娘。", Some("。")),
+    ];
+    assert_eq!(actual, expected);
+
+    let result = SegmenterBuilder::new()
+        .in_delimiters(["。"])
+        .force_break_words([""])
+        .build();
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_force_break_regex() {
+    let seg = SegmenterBuilder::new()
+        .in_delimiters(["。"])
+        .quotes([('「', '」')])
+        .no_break_regex(Regex::new(r"(前文\n)").unwrap())
+        .force_break_regex(Regex::new(r"\n(第\d+条)").unwrap())
+        .force_break_regex(Regex::new(r"―{2,}").unwrap())
+        .build()
+        .unwrap();
+    let text = "「前文\n第1条　目的。\n第12条――定義。」";
     let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
-    let expected = vec!["はぁ。。。。。。", "疲れた。", "。"];
+    let expected = vec!["「前文\n", "第1条　目的。\n", "第12条――", "定義。」"];
     assert_eq!(sentences, expected);
 }
 
 #[test]
-fn test_no_last_delimiter() {
+fn test_mergeable_delimiters() {
+    let seg = SegmenterBuilder::new()
+        .in_delimiters(["。", "！", "？", "?", "!"])
+        .ex_delimiters(["\n"])
+        .mergeable_delimiters(["。", "！", "？", "?", "!"])
+        .trailing_attachers(["」"])
+        .no_break_words(["娘。"])
+        .build()
+        .unwrap();
+    let text = "えっ！？本当?!\nはぁ。。。」疲れた。！娘。。娘。";
+    let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+    let expected = vec![
+        "えっ！？",
+        "本当?!",
+        "はぁ。。。」",
+        "疲れた。！",
+        "娘。。",
+        "娘。",
+    ];
+    assert_eq!(sentences, expected);
+
+    // Only the given delimiters are merged.
+    let seg = SegmenterBuilder::new()
+        .in_delimiters(["。", "！"])
+        .mergeable_delimiters(["。"])
+        .build()
+        .unwrap();
+    let text = "はぁ。。！！";
+    let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+    let expected = vec!["はぁ。。", "！", "！"];
+    assert_eq!(sentences, expected);
+
+    // Delimiters are not merged across forced breaks.
     let seg = SegmenterBuilder::new()
         .in_delimiters(["。"])
+        .mergeable_delimiters(["。"])
+        .force_break_regex(Regex::new("。(。)").unwrap())
         .build()
         .unwrap();
-    let text = "これはペンです。それはマーカーです";
+    let text = "はぁ。。。";
     let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
-    let expected = vec!["これはペンです。", "それはマーカーです"];
+    let expected = vec!["はぁ。", "。。"];
     assert_eq!(sentences, expected);
+
+    let result = SegmenterBuilder::new()
+        .in_delimiters(["。"])
+        .mergeable_delimiters(["。", ""])
+        .build();
+    assert!(result.is_err());
 }
 
 #[test]
-fn test_empty_text() {
+fn test_trailing_attachers() {
+    let seg = SegmenterBuilder::new()
+        .in_delimiters(["。", "！"])
+        .ex_delimiters(["\n"])
+        .trailing_attachers(["」", "）", "』", "※1"])
+        .quotes([('（', '）')])
+        .no_break_words(["娘。"])
+        .build()
+        .unwrap();
+    let text = "行った。」と言った。※1\n終わり。』）次は（笑。）娘。」です！！";
+    let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+    let expected = vec![
+        "行った。」",
+        "と言った。※1",
+        "終わり。』）",
+        "次は（笑。）娘。」です！",
+        "！",
+    ];
+    assert_eq!(sentences, expected);
+
+    // Attachers are not attached across forced breaks.
     let seg = SegmenterBuilder::new()
         .in_delimiters(["。"])
+        .trailing_attachers(["」"])
+        .force_break_words(["。"])
         .build()
         .unwrap();
-    let text = "";
+    let text = "行った。」と";
     let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
-    assert!(sentences.is_empty());
+    let expected = vec!["行った。", "」と"];
+    assert_eq!(sentences, expected);
+
+    let result = SegmenterBuilder::new()
+        .in_delimiters(["。"])
+        .trailing_attachers(["」", ""])
+        .build();
+    assert!(result.is_err());
 }
 
 #[test]
-fn test_empty_lines() {
+fn test_bounded_delimiters() {
+    let seg = SegmenterBuilder::new()
+        .in_delimiters(["！"])
+        .in_delimiters_with_boundaries(
+            ["。", "。」"],
+            WordBoundary::Any,
+            WordBoundary::Class("[^」）]".to_string()),
+        )
+        .ex_delimiters_with_boundaries(
+            ["\n"],
+            WordBoundary::Class("[。！」]".to_string()),
+            WordBoundary::Any,
+        )
+        .build()
+        .unwrap();
+    let text = "はい（笑。）いいえ。」と\n言った。\n「はい。」そう！";
+    let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+    let expected = vec![
+        "はい（笑。）いいえ。」",
+        "と\n言った。",
+        "「はい。」",
+        "そう！",
+    ];
+    assert_eq!(sentences, expected);
+
+    let result = SegmenterBuilder::new()
+        .in_delimiters_with_boundaries(
+            ["。"],
+            WordBoundary::Any,
+            WordBoundary::Class("^」".to_string()),
+        )
+        .build();
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_empty_delimiters() {
+    let result = SegmenterBuilder::new().in_delimiters([""]).build();
+    assert!(matches!(result, Err(EasySegmenterError::Input(_))));
+    let result = SegmenterBuilder::new()
+        .in_delimiters(["。"])
+        .ex_delimiters([""])
+        .build();
+    assert!(matches!(result, Err(EasySegmenterError::Input(_))));
+    let result = SegmenterBuilder::new()
+        .in_delimiters(["。"])
+        .ex_delimiters_with_boundaries([""], WordBoundary::Any, WordBoundary::NonAlphanumeric)
+        .build();
+    assert!(matches!(result, Err(EasySegmenterError::Input(_))));
+
+    let result = SegmenterBuilder::from_toml_str(
+        r#"
+        in_delimiters = ["。"]
+        [[bounded_delimiters]]
+        in_delimiters = [""]
+        "#,
+    );
+    let msg = result.err().unwrap().to_string();
+    assert!(
+        msg.contains("bounded_delimiters[0].in_delimiters[0]"),
+        "{msg}"
+    );
+}
+
+#[test]
+fn test_delimiter_regex() {
     let seg = SegmenterBuilder::new()
+        .in_delimiters(["。"])
         .ex_delimiters(["\n"])
+        .in_delimiter_regex(Regex::new(r"(\. )[A-Z]").unwrap())
+        .ex_delimiter_regex(Regex::new(r"\n{2,}").unwrap())
+        .quotes([('「', '」')])
         .build()
         .unwrap();
-    let text = "これはペンです\n\n\nそれはマーカーです";
+    let text = "Mr. smith came. He left\n\n\n「No. Yes。」と。\nOK";
     let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
-    let expected = vec!["これはペンです", "それはマーカーです"];
+    let expected = vec!["Mr. smith came. ", "He left", "「No. Yes。」と。", "OK"];
+    assert_eq!(sentences, expected);
+
+    let seg = SegmenterBuilder::new()
+        .in_delimiter_regex(Regex::new(r"(。+)").unwrap())
+        .build()
+        .unwrap();
+    let text = "はい。。いいえ。";
+    let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+    let expected = vec!["はい。。", "いいえ。"];
     assert_eq!(sentences, expected);
 }
+
+#[test]
+fn test_word_parts() {
+    let seg = SegmenterBuilder::new()
+        .in_delimiters(["．", "。"])
+        .no_break_word_patterns(["e[．]g．", "[モーニング娘。]"])
+        .build()
+        .unwrap();
+    let text = "例えばe．g．モーニング娘。と。";
+    let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+    let expected = vec!["例えばe．g．", "モーニング娘。と。"];
+    assert_eq!(sentences, expected);
+
+    let seg = SegmenterBuilder::new()
+        .in_delimiters(["．"])
+        .no_break_word_patterns(["e[．]g．"])
+        .word_normalization(WordNormalization::FoldWidth)
+        .build()
+        .unwrap();
+    let text = "例えばe.g.と．ｅ．ｇ．";
+    let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+    let expected = vec!["例えばe.g.と．", "ｅ．ｇ．"];
+    assert_eq!(sentences, expected);
+
+    let result = SegmenterBuilder::new()
+        .in_delimiters(["．"])
+        .no_break_word_patterns(["e[．g．"])
+        .build();
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_words_with_brackets() {
+    // Brackets and backslashes in no-break words are literal characters.
+    let seg = SegmenterBuilder::new()
+        .in_delimiters(["。"])
+        .no_break_words(["[略]。", r"\[注。", "】。"])
+        .build()
+        .unwrap();
+    let text = r"本文[略]。と\[注。と】。です。";
+    let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+    let expected = vec![r"本文[略]。と\[注。と】。です。"];
+    assert_eq!(sentences, expected);
+    let text = "本文略。です。";
+    let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+    let expected = vec!["本文略。", "です。"];
+    assert_eq!(sentences, expected);
+}
+
+#[cfg(feature = "nfkc")]
+#[test]
+fn test_word_normalization_nfkc() {
+    let seg = SegmenterBuilder::new()
+        .in_delimiters(["。", "．"])
+        .no_break_words(["No.", "株式会社。"])
+        .word_normalization(WordNormalization::Nfkc)
+        .build()
+        .unwrap();
+    let text = "№．１です。㍿。です。";
+    let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+    let expected = vec!["№．１です。", "㍿。です。"];
+    assert_eq!(sentences, expected);
+}
+
+#[test]
+fn test_regex_1() {
+    let seg = SegmenterBuilder::new()
+        .in_delimiters(["．"])
+        .no_break_regex(Regex::new(r"\d(．)\d").unwrap())
+        .build()
+        .unwrap();
+    let text = "３．１４１５９２．円周率です．";
+    let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+    let expected = vec!["３．１４１５９２．", "円周率です．"];
+    assert_eq!(sentences, expected);
+}
+
+#[test]
+fn test_regex_2() {
+    let seg = SegmenterBuilder::new()
+        .in_delimiters(["。"])
+        .no_break_regex(Regex::new(r"(。{2,})。").unwrap())
+        .build()
+        .unwrap();
+    let text = "はぁ。。。。。。疲れた。。";
+    let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+    let expected = vec!["はぁ。。。。。。", "疲れた。", "。"];
+    assert_eq!(sentences, expected);
+}
+
+#[test]
+fn test_no_last_delimiter() {
+    let seg = SegmenterBuilder::new()
+        .in_delimiters(["。"])
+        .build()
+        .unwrap();
+    let text = "これはペンです。それはマーカーです";
+    let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+    let expected = vec!["これはペンです。", "それはマーカーです"];
+    assert_eq!(sentences, expected);
+}
+
+#[test]
+fn test_empty_text() {
+    let seg = SegmenterBuilder::new()
+        .in_delimiters(["。"])
+        .build()
+        .unwrap();
+    let text = "";
+    let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+    assert!(sentences.is_empty());
+}
+
+#[test]
+fn test_empty_lines() {
+    let seg = SegmenterBuilder::new()
+        .ex_delimiters(["\n"])
+        .build()
+        .unwrap();
+    let text = "これはペンです\n\n\nそれはマーカーです";
+    let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+    let expected = vec!["これはペンです", "それはマーカーです"];
+    assert_eq!(sentences, expected);
+}
+
+#[test]
+fn test_trim_policy() {
+    let text = " \tはい。\u{3000}いいえ \n　\n";
+    for (policy, expected) in [
+        (TrimPolicy::None, vec![" \tはい。", "\u{3000}いいえ ", "　"]),
+        (TrimPolicy::Ascii, vec!["はい。", "\u{3000}いいえ", "　"]),
+        (TrimPolicy::Unicode, vec!["はい。", "いいえ", ""]),
+        (
+            TrimPolicy::Class("[　]".to_string()),
+            vec![" \tはい。", "いいえ ", ""],
+        ),
+    ] {
+        let seg = SegmenterBuilder::new()
+            .in_delimiters(["。"])
+            .ex_delimiters(["\n"])
+            .trim_policy(policy)
+            .build()
+            .unwrap();
+        let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+        assert_eq!(sentences, expected);
+    }
+
+    let seg = SegmenterBuilder::new()
+        .in_delimiters(["。"])
+        .ex_delimiters(["\n"])
+        .trim_policy(TrimPolicy::Unicode)
+        .build()
+        .unwrap();
+    let sentences: Vec<_> = seg.sentences(text).collect();
+    assert_eq!(sentences[1].byte_range(), 14..23);
+    assert_eq!(sentences[1].char_range(), 6..9);
+    assert_eq!(sentences[2].byte_range(), 28..28);
+    assert_eq!(sentences[2].index(), 2);
+
+    let result = SegmenterBuilder::new()
+        .in_delimiters(["。"])
+        .trim_policy(TrimPolicy::Class(" ".to_string()))
+        .build();
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_trim_inclusive_delimiter() {
+    let seg = SegmenterBuilder::new()
+        .in_delimiters(["。 ", "\n"])
+        .trim_policy(TrimPolicy::Ascii)
+        .build()
+        .unwrap();
+    let text = "はい。 いいえ\n\n";
+    let sentences: Vec<_> = seg.sentences(text).collect();
+    assert_eq!(sentences.len(), 3);
+    for s in &sentences {
+        let (range, delimiter) = (s.byte_range(), s.delimiter().unwrap().byte_range());
+        assert!(range.start <= delimiter.start && delimiter.end <= range.end);
+    }
+    assert_eq!(sentences[0].as_str(), "はい。");
+    assert_eq!(sentences[0].delimiter().unwrap().as_str(), "。");
+    assert_eq!(sentences[0].delimiter().unwrap().byte_range(), 6..9);
+    assert_eq!(sentences[1].as_str(), "いいえ");
+    assert_eq!(sentences[1].delimiter().unwrap().byte_range(), 19..19);
+    assert_eq!(sentences[2].byte_range(), 21..21);
+    assert_eq!(sentences[2].delimiter().unwrap().byte_range(), 21..21);
+}
+
+#[test]
+fn test_drop_empty_sentences() {
+    let seg = SegmenterBuilder::new()
+        .in_delimiters(["。"])
+        .ex_delimiters(["\n"])
+        .trim_policy(TrimPolicy::Unicode)
+        .drop_empty_sentences(true)
+        .build()
+        .unwrap();
+    let text = "　はい。 \n　\n\tいいえ\n ";
+    let sentences: Vec<_> = seg.sentences(text).collect();
+    assert_eq!(sentences.len(), 2);
+    assert_eq!(sentences[0].as_str(), "はい。");
+    assert_eq!(sentences[1].as_str(), "いいえ");
+    assert_eq!(sentences[1].index(), 1);
+
+    let mut scratch = SegmentScratch::new();
+    let expected: Vec<_> = seg.segment(text).collect();
+    assert_eq!(
+        seg.segment_with(text, &mut scratch).collect::<Vec<_>>(),
+        expected
+    );
+}
+
+#[test]
+fn test_toml_1() {
+    let seg = SegmenterBuilder::from_toml_str(
+        r#"
+        in_delimiters = ["。", "．"]
+        ex_delimiters = ["\n"]
+        quotes = ["「」"]
+        words = ["モーニング娘。"]
+        [regex]
+        decimal_point = '\d(．)\d'
+        "#,
+    )
+    .unwrap()
+    .build()
+    .unwrap();
+    let text = "モーニング娘。は「はい。」と答えた。\n円周率は３．１４です．";
+    let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+    let expected = vec![
+        "モーニング娘。は「はい。」と答えた。",
+        "円周率は３．１４です．",
+    ];
+    assert_eq!(sentences, expected);
+}
+
+#[test]
+fn test_toml_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("rules.toml");
+    std::fs::write(&path, r#"in_delimiters = ["。"]"#).unwrap();
+    let seg = SegmenterBuilder::from_toml_file(&path)
+        .unwrap()
+        .build()
+        .unwrap();
+    let text = "これはペンです。それはマーカーです。";
+    let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+    let expected = vec!["これはペンです。", "それはマーカーです。"];
+    assert_eq!(sentences, expected);
+}
+
+#[test]
+fn test_toml_invalid_quote() {
+    let result = SegmenterBuilder::from_toml_str(r#"quotes = ["「」", "（"]"#);
+    let msg = result.err().unwrap().to_string();
+    assert!(msg.contains(r#"quotes[1] = "（""#), "{msg}");
+}
+
+#[test]
+fn test_toml_duplicate_quote() {
+    let result = SegmenterBuilder::from_toml_str(r#"quotes = ["「」", "（」"]"#);
+    let msg = result.err().unwrap().to_string();
+    assert!(msg.contains(r#"quotes[1] = "（」""#), "{msg}");
+}
+
+#[test]
+fn test_toml_symmetric_quote() {
+    let builder = SegmenterBuilder::from_toml_str(
+        r#"
+        in_delimiters = ["。"]
+        quotes = ['""', "「」"]
+        symmetric_quote_rule = "word_boundary"
+        "#,
+    )
+    .unwrap();
+    let toml_str = builder.to_toml_string().unwrap();
+    assert!(toml_str.contains(r#"symmetric_quote_rule = "word_boundary""#));
+    let seg = builder.build().unwrap();
+    let text = "彼は\"はい。\"と言った。";
+    assert_eq!(seg.segment(text).count(), 1);
+
+    let result = SegmenterBuilder::from_toml_str(r#"quotes = ['""', '"」']"#);
+    let msg = result.err().unwrap().to_string();
+    assert!(msg.contains("quotes[1]"), "{msg}");
+}
+
+#[test]
+fn test_toml_quote_pairs() {
+    let builder = SegmenterBuilder::from_toml_str(
+        r#"
+        in_delimiters = ["。"]
+        quotes = ["「」"]
+        quote_pairs = [["<q>", "</q>"], ["```", "```"]]
+        "#,
+    )
+    .unwrap();
+    let toml_str = builder.to_toml_string().unwrap();
+    assert!(toml_str.contains(r#"quotes = ["「」"]"#), "{toml_str}");
+    assert!(
+        toml_str.contains(r#"quote_pairs = [["<q>", "</q>"], ["```", "```"]]"#),
+        "{toml_str}"
+    );
+    let seg = builder.build().unwrap();
+    let text = "彼は<q>はい。</q>と言った。";
+    assert_eq!(seg.segment(text).count(), 1);
+
+    let result = SegmenterBuilder::from_toml_str(r#"quote_pairs = [["<q>", ""]]"#);
+    let msg = result.err().unwrap().to_string();
+    assert!(msg.contains("quote_pairs[0]"), "{msg}");
+
+    let result = SegmenterBuilder::from_toml_str(
+        r#"
+        quotes = ["<>"]
+        quote_pairs = [["<", ">>"]]
+        "#,
+    );
+    let msg = result.err().unwrap().to_string();
+    assert!(msg.contains("quote_pairs[0]"), "{msg}");
+}
+
+#[test]
+fn test_toml_quote_recovery() {
+    let builder = SegmenterBuilder::from_toml_str(
+        r#"
+        in_delimiters = ["。"]
+        ex_delimiters = ["\n"]
+        quotes = ["「」", "（）"]
+        max_quote_len = 100
+        quote_reset_delimiters = ["\n\n"]
+        quote_mismatch_policy = "pop_to_match"
+        "#,
+    )
+    .unwrap();
+    let toml_str = builder.to_toml_string().unwrap();
+    assert!(toml_str.contains("max_quote_len = 100"), "{toml_str}");
+    assert!(
+        toml_str.contains(r#"quote_reset_delimiters = ["\n\n"]"#),
+        "{toml_str}"
+    );
+    assert!(
+        toml_str.contains(r#"quote_mismatch_policy = "pop_to_match""#),
+        "{toml_str}"
+    );
+    let seg = builder.build().unwrap();
+    let text = "「はい（そう。」次。";
+    assert_eq!(seg.segment(text).count(), 1);
+
+    let result = SegmenterBuilder::from_toml_str("max_quote_len = 0");
+    let msg = result.err().unwrap().to_string();
+    assert!(msg.contains("max_quote_len = 0"), "{msg}");
+
+    let result = SegmenterBuilder::from_toml_str(r#"quote_reset_delimiters = [""]"#);
+    let msg = result.err().unwrap().to_string();
+    assert!(msg.contains("quote_reset_delimiters[0]"), "{msg}");
+}
+
+#[test]
+fn test_toml_quote_policies() {
+    let builder = SegmenterBuilder::from_toml_str(
+        r#"
+        in_delimiters = ["。"]
+        quotes = ["「」", "（）"]
+        [quote_policies]
+        "「" = "split_inside"
+        "（" = { split_if_longer_than = 5 }
+        "#,
+    )
+    .unwrap();
+    let toml_str = builder.to_toml_string().unwrap();
+    let restored = SegmenterBuilder::from_toml_str(&toml_str).unwrap();
+    assert_eq!(restored.to_rule_config(), builder.to_rule_config());
+    let seg = builder.build().unwrap();
+    let text = "「はい。そう。」（嘘。）（長い。嘘。）";
+    let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+    let expected = vec!["「はい。", "そう。」（嘘。）（長い。", "嘘。）"];
+    assert_eq!(sentences, expected);
+
+    let result = SegmenterBuilder::from_toml_str(
+        r#"
+        quotes = ["「」"]
+        [quote_policies]
+        "」" = "split_inside"
+        "#,
+    );
+    let msg = result.err().unwrap().to_string();
+    assert!(msg.contains("quote_policies"), "{msg}");
+}
+
+#[test]
+fn test_words_from_reader() {
+    let dict = "# Comment\r\nモーニング娘。\r\n\n  \n\\#Hash。\n\\Back。\n[略]。";
+    let seg = SegmenterBuilder::new()
+        .in_delimiters(["。", "."])
+        .no_break_words_from_reader(dict.as_bytes())
+        .unwrap()
+        .build()
+        .unwrap();
+    let text = "モーニング娘。#Hash。\\Back。[略]。です。# Comment。";
+    let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+    let expected = vec!["モーニング娘。#Hash。\\Back。[略]。です。", "# Comment。"];
+    assert_eq!(sentences, expected);
+
+    let result = SegmenterBuilder::new().no_break_words_from_reader(&b"\xff\n"[..]);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_words_from_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("words.txt");
+    std::fs::write(&path, "# Idol groups\nモーニング娘。\n").unwrap();
+    let seg = SegmenterBuilder::new()
+        .in_delimiters(["。"])
+        .no_break_words_from_file(&path)
+        .unwrap()
+        .word_matcher_kind(WordMatcherKind::Nfa)
+        .build()
+        .unwrap();
+    let text = "モーニング娘。の新曲。";
+    let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+    let expected = vec!["モーニング娘。の新曲。"];
+    assert_eq!(sentences, expected);
+
+    let result = SegmenterBuilder::new().no_break_words_from_file(dir.path().join("missing.txt"));
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_toml_mergeable_delimiters() {
+    let builder = SegmenterBuilder::from_toml_str(
+        r#"
+        in_delimiters = ["。", "！", "？"]
+        mergeable_delimiters = ["！", "？"]
+        "#,
+    )
+    .unwrap();
+    let toml_str = builder.to_toml_string().unwrap();
+    assert!(
+        toml_str.contains(r#"mergeable_delimiters = ["！", "？"]"#),
+        "{toml_str}"
+    );
+    let seg = builder.build().unwrap();
+    let text = "えっ！？はい。。";
+    let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+    let expected = vec!["えっ！？", "はい。", "。"];
+    assert_eq!(sentences, expected);
+
+    let result = SegmenterBuilder::from_toml_str(
+        r#"
+        in_delimiters = ["。"]
+        mergeable_delimiters = ["。", ""]
+        "#,
+    );
+    let msg = result.err().unwrap().to_string();
+    assert!(msg.contains(r#"mergeable_delimiters[1] = """#), "{msg}");
+}
+
+#[test]
+fn test_toml_trailing_attachers() {
+    let builder = SegmenterBuilder::from_toml_str(
+        r#"
+        in_delimiters = ["。"]
+        trailing_attachers = ["」", "）"]
+        "#,
+    )
+    .unwrap();
+    let toml_str = builder.to_toml_string().unwrap();
+    assert!(
+        toml_str.contains(r#"trailing_attachers = ["」", "）"]"#),
+        "{toml_str}"
+    );
+    let seg = builder.build().unwrap();
+    let text = "行った。」と言った。）";
+    let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+    let expected = vec!["行った。」", "と言った。）"];
+    assert_eq!(sentences, expected);
+
+    let result = SegmenterBuilder::from_toml_str(
+        r#"
+        in_delimiters = ["。"]
+        trailing_attachers = ["」", ""]
+        "#,
+    );
+    let msg = result.err().unwrap().to_string();
+    assert!(msg.contains(r#"trailing_attachers[1] = """#), "{msg}");
+}
+
+#[test]
+fn test_toml_bounded_delimiters() {
+    let builder = SegmenterBuilder::from_toml_str(
+        r#"
+        in_delimiters = ["！"]
+        [[bounded_delimiters]]
+        in_delimiters = ["。"]
+        after = "[^」）]"
+        [[bounded_delimiters]]
+        ex_delimiters = ["\n"]
+        before = "[。！」]"
+        "#,
+    )
+    .unwrap();
+    let toml_str = builder.to_toml_string().unwrap();
+    let rebuilt = SegmenterBuilder::from_toml_str(&toml_str).unwrap();
+    assert_eq!(rebuilt.to_rule_config(), builder.to_rule_config());
+    let seg = builder.build().unwrap();
+    let text = "はい（笑。）いいえ。と\n言った。\nそう！";
+    let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+    let expected = vec!["はい（笑。）いいえ。", "と\n言った。", "そう！"];
+    assert_eq!(sentences, expected);
+
+    let result = SegmenterBuilder::from_toml_str(
+        r#"
+        [[bounded_delimiters]]
+        ex_delimiters = ["\n", ""]
+        "#,
+    );
+    let msg = result.err().unwrap().to_string();
+    assert!(
+        msg.contains(r#"bounded_delimiters[0].ex_delimiters[1] = """#),
+        "{msg}"
+    );
+}
+
+#[test]
+fn test_toml_delimiter_regex() {
+    let builder = SegmenterBuilder::from_toml_str(
+        r#"
+        in_delimiters = ["。"]
+        [in_delimiter_regex]
+        period = '(\. )[A-Z]'
+        [ex_delimiter_regex]
+        blank_lines = '\n{2,}'
+        "#,
+    )
+    .unwrap();
+    let toml_str = builder.to_toml_string().unwrap();
+    assert!(toml_str.contains("[in_delimiter_regex]"), "{toml_str}");
+    assert!(toml_str.contains("[ex_delimiter_regex]"), "{toml_str}");
+    let seg = builder.build().unwrap();
+    let text = "3. 5. OK。\n\nはい";
+    let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+    let expected = vec!["3. 5. ", "OK。", "はい"];
+    assert_eq!(sentences, expected);
+
+    let result = SegmenterBuilder::from_toml_str(
+        r#"
+        [ex_delimiter_regex]
+        blank_lines = '(\n'
+        "#,
+    );
+    let msg = result.err().unwrap().to_string();
+    assert!(msg.contains("ex_delimiter_regex.blank_lines"), "{msg}");
+}
+
+#[test]
+fn test_toml_force_break() {
+    let builder = SegmenterBuilder::from_toml_str(
+        r#"
+        in_delimiters = ["。"]
+        quotes = ["「」"]
+        force_break_words = ["\n\n"]
+        [force_break_regex]
+        article = '\n(第\d+条)'
+        "#,
+    )
+    .unwrap();
+    let toml_str = builder.to_toml_string().unwrap();
+    assert!(
+        toml_str.contains(r#"force_break_words = ["\n\n"]"#),
+        "{toml_str}"
+    );
+    assert!(toml_str.contains("[force_break_regex]"), "{toml_str}");
+    let seg = builder.build().unwrap();
+    let text = "「はい。\n\nいいえ\n第1条。」";
+    let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+    let expected = vec!["「はい。\n\n", "いいえ\n", "第1条。」"];
+    assert_eq!(sentences, expected);
+
+    let result = SegmenterBuilder::from_toml_str(
+        r#"
+        in_delimiters = ["。"]
+        force_break_words = ["\n", ""]
+        "#,
+    );
+    let msg = result.err().unwrap().to_string();
+    assert!(msg.contains(r#"force_break_words[1] = """#), "{msg}");
+
+    let result = SegmenterBuilder::from_toml_str(
+        r#"
+        in_delimiters = ["。"]
+        [force_break_regex]
+        article = '(第'
+        "#,
+    );
+    let msg = result.err().unwrap().to_string();
+    assert!(msg.contains("force_break_regex.article"), "{msg}");
+}
+
+#[test]
+fn test_toml_word_matcher_kind() {
+    let builder = SegmenterBuilder::from_toml_str(
+        r#"
+        in_delimiters = ["。"]
+        words = ["モーニング娘。"]
+        word_matcher_kind = "nfa"
+        "#,
+    )
+    .unwrap();
+    let toml_str = builder.to_toml_string().unwrap();
+    assert!(
+        toml_str.contains(r#"word_matcher_kind = "nfa""#),
+        "{toml_str}"
+    );
+    let seg = builder.build().unwrap();
+    let text = "モーニング娘。の新曲";
+    assert_eq!(seg.segment(text).count(), 1);
+}
+
+#[test]
+fn test_toml_trim_policy() {
+    let builder = SegmenterBuilder::from_toml_str(
+        r#"
+        in_delimiters = ["。"]
+        ex_delimiters = ["\n"]
+        trim_policy = "[ 　]"
+        drop_empty_sentences = true
+        "#,
+    )
+    .unwrap();
+    let toml_str = builder.to_toml_string().unwrap();
+    assert!(toml_str.contains(r#"trim_policy = "[ 　]""#), "{toml_str}");
+    assert!(
+        toml_str.contains("drop_empty_sentences = true"),
+        "{toml_str}"
+    );
+    let seg = builder.build().unwrap();
+    let text = "　はい。\n 　\nいいえ";
+    let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+    let expected = vec!["はい。", "いいえ"];
+    assert_eq!(sentences, expected);
+
+    let builder = SegmenterBuilder::from_toml_str(
+        r#"
+        in_delimiters = ["。"]
+        trim_policy = "unicode"
+        "#,
+    )
+    .unwrap();
+    let toml_str = builder.to_toml_string().unwrap();
+    assert!(
+        toml_str.contains(r#"trim_policy = "unicode""#),
+        "{toml_str}"
+    );
+
+    let result = SegmenterBuilder::from_toml_str(
+        r#"
+        in_delimiters = ["。"]
+        trim_policy = "space"
+        "#,
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_toml_word_normalization() {
+    let builder = SegmenterBuilder::from_toml_str(
+        r#"
+        in_delimiters = ["．"]
+        words = ["Mr."]
+        word_ascii_case_insensitive = true
+        word_normalization = "fold_width"
+        "#,
+    )
+    .unwrap();
+    let toml_str = builder.to_toml_string().unwrap();
+    assert!(
+        toml_str.contains("word_ascii_case_insensitive = true"),
+        "{toml_str}"
+    );
+    assert!(
+        toml_str.contains(r#"word_normalization = "fold_width""#),
+        "{toml_str}"
+    );
+    let seg = builder.build().unwrap();
+    let text = "ＭＲ．田中．";
+    assert_eq!(seg.segment(text).count(), 1);
+
+    let toml_str = SegmenterBuilder::new().to_toml_string().unwrap();
+    assert!(!toml_str.contains("word_"), "{toml_str}");
+}
+
+#[test]
+fn test_toml_bounded_words() {
+    let builder = SegmenterBuilder::from_toml_str(
+        r#"
+        in_delimiters = [". "]
+        words = ["e.g. "]
+        [[bounded_words]]
+        words = ["St. ", "Mr. "]
+        before = "non_alphanumeric"
+        "#,
+    )
+    .unwrap()
+    .no_break_words_with_boundaries(["Dr. "], WordBoundary::NonAlphanumeric, WordBoundary::Any);
+    let config = builder.to_rule_config();
+    assert_eq!(config.words, vec!["e.g. ".to_string()]);
+    assert_eq!(config.bounded_words.len(), 1);
+    assert_eq!(config.bounded_words[0].words, vec!["St. ", "Mr. ", "Dr. "]);
+    let toml_str = builder.to_toml_string().unwrap();
+    let restored = SegmenterBuilder::from_toml_str(&toml_str).unwrap();
+    assert_eq!(restored.to_rule_config(), config);
+    let seg = builder.build().unwrap();
+    let text = "He was 1st. Dr. Who, e.g. the doctor. ";
+    let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+    let expected = vec!["He was 1st. ", "Dr. Who, e.g. the doctor. "];
+    assert_eq!(sentences, expected);
+
+    let result = SegmenterBuilder::from_toml_str(
+        r#"
+        [[bounded_words]]
+        words = ["St.", ""]
+        "#,
+    );
+    let msg = result.err().unwrap().to_string();
+    assert!(msg.contains("bounded_words[0].words[1]"), "{msg}");
+}
+
+#[test]
+fn test_toml_word_parts() {
+    let result = SegmenterBuilder::from_toml_str(r#"word_patterns = ["e[.]g.", "e[.g."]"#);
+    let msg = result.err().unwrap().to_string();
+    assert!(msg.contains(r#"word_patterns[1] = "e[.g.""#), "{msg}");
+    assert!(msg.contains("Brackets"), "{msg}");
+
+    // Words and bounded words are literal.
+    let builder = SegmenterBuilder::from_toml_str(
+        r#"
+        in_delimiters = ["."]
+        words = ["e[.g."]
+        word_patterns = ["e[.]g."]
+
+        [[bounded_words]]
+        words = ["St[.]]"]
+        "#,
+    )
+    .unwrap();
+    let config = builder.to_rule_config();
+    assert_eq!(config.words, vec!["e[.g.", "St[.]]"]);
+    assert_eq!(config.word_patterns, vec!["e[.]g."]);
+    let seg = builder.build().unwrap();
+    let text = "e[.g.St[.]]e.g.";
+    let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+    let expected = vec!["e[.g.St[.]]e.g."];
+    assert_eq!(sentences, expected);
+}
+
+#[test]
+fn test_toml_invalid_regex() {
+    let result = SegmenterBuilder::from_toml_str(
+        r#"
+        [regex]
+        broken = '\d(．'
+        "#,
+    );
+    let msg = result.err().unwrap().to_string();
+    assert!(msg.contains("regex.broken"), "{msg}");
+}
+
+#[test]
+fn test_toml_empty_delimiter() {
+    let result = SegmenterBuilder::from_toml_str(r#"ex_delimiters = ["\n", ""]"#);
+    let msg = result.err().unwrap().to_string();
+    assert!(msg.contains(r#"ex_delimiters[1] = """#), "{msg}");
+}
+
+#[test]
+fn test_toml_missing_file() {
+    let result = SegmenterBuilder::from_toml_file("/nonexistent/easy_segmenter.toml");
+    assert!(matches!(result, Err(EasySegmenterError::Io(_))));
+}
+
+#[test]
+fn test_toml_round_trip() {
+    let builder = SegmenterBuilder::new()
+        .in_delimiters(["。", "．"])
+        .ex_delimiters(["\n", "\r\n"])
+        .quotes([('「', '」'), ('（', '）')])
+        .no_break_words(["モーニング娘。"])
+        .no_break_regex(Regex::new(r"\d(．)\d").unwrap())
+        .max_quote_level(2)
+        .unwrap();
+    let toml_str = builder.to_toml_string().unwrap();
+    let restored = SegmenterBuilder::from_toml_str(&toml_str).unwrap();
+    assert_eq!(restored.to_toml_string().unwrap(), toml_str);
+    assert_eq!(restored.to_rule_config(), builder.to_rule_config());
+
+    let text = "モーニング娘。は「はい。」と（３．１４。）\n答えた．";
+    let seg = builder.build().unwrap();
+    let restored = restored.build().unwrap();
+    assert_eq!(
+        seg.segment(text).collect::<Vec<_>>(),
+        restored.segment(text).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_toml_regex_names() {
+    let builder = SegmenterBuilder::from_toml_str(
+        r#"
+        in_delimiters = ["。"]
+        [regex]
+        regex0 = '(。{2,})。'
+        "#,
+    )
+    .unwrap()
+    .no_break_regex(Regex::new(r"\d(．)\d").unwrap());
+    let config = builder.to_rule_config();
+    assert_eq!(config.regex["regex0"], r"(。{2,})。");
+    assert_eq!(config.regex["regex1"], r"\d(．)\d");
+}
+
+#[test]
+fn test_toml_regex_order() {
+    let patterns: Vec<_> = (0..12).map(|i| format!("(。){i}")).collect();
+    let mut builder = SegmenterBuilder::new();
+    for p in &patterns {
+        builder = builder.in_delimiter_regex(Regex::new(p).unwrap());
+    }
+    // The names sort in the order of registration, which is kept in rule files.
+    let config = builder.to_rule_config();
+    let names: Vec<_> = config.in_delimiter_regex.keys().cloned().collect();
+    assert_eq!(names[..3], ["regex00", "regex01", "regex02"]);
+    assert_eq!(
+        config.in_delimiter_regex.values().collect::<Vec<_>>(),
+        patterns.iter().collect::<Vec<_>>()
+    );
+    let restored = SegmenterBuilder::from_rule_config(config.clone()).unwrap();
+    assert_eq!(restored.to_rule_config(), config);
+}
+
+#[test]
+fn test_toml_extends() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(dir.path().join("domain")).unwrap();
+    std::fs::write(dir.path().join("base.toml"), r#"extends = ["template:ja"]"#).unwrap();
+    std::fs::write(
+        dir.path().join("domain/legal.toml"),
+        r#"
+        extends = ["../base.toml"]
+        words = ["モーニング娘。"]
+        "#,
+    )
+    .unwrap();
+    let seg = SegmenterBuilder::from_toml_file(dir.path().join("domain/legal.toml"))
+        .unwrap()
+        .build()
+        .unwrap();
+    let text = "モーニング娘。は「はい。」と答えた！３．１４です。";
+    let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+    let expected = vec!["モーニング娘。は「はい。」と答えた！", "３．１４です。"];
+    assert_eq!(sentences, expected);
+}
+
+#[test]
+fn test_toml_template_remove_delimiters() {
+    let toml_str = r#"
+        extends = ["template:ja"]
+        remove_delimiters = ["！"]
+    "#;
+    let seg = SegmenterBuilder::from_toml_str(toml_str)
+        .unwrap()
+        .build()
+        .unwrap();
+    let text = "はい。！次は？？";
+    let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+    let expected = vec!["はい。", "！次は？？"];
+    assert_eq!(sentences, expected);
+}
+
+#[test]
+fn test_sentences_1() {
+    let seg = SegmenterBuilder::new()
+        .in_delimiters(["。"])
+        .ex_delimiters(["\n"])
+        .build()
+        .unwrap();
+    let text = "aはペンです。\n\nbはマーカー";
+    let sentences: Vec<_> = seg.sentences(text).collect();
+    assert_eq!(sentences.len(), 2);
+
+    assert_eq!(sentences[0].as_str(), "aはペンです。");
+    assert_eq!(sentences[0].byte_range(), 0..19);
+    assert_eq!(sentences[0].char_range(), 0..7);
+    assert_eq!(sentences[0].index(), 0);
+    let delimiter = sentences[0].delimiter().unwrap();
+    assert_eq!(delimiter.as_str(), "。");
+    assert_eq!(delimiter.byte_range(), 16..19);
+    assert!(delimiter.is_inclusive());
+
+    assert_eq!(sentences[1].as_str(), "bはマーカー");
+    assert_eq!(sentences[1].byte_range(), 21..37);
+    assert_eq!(sentences[1].char_range(), 9..15);
+    assert_eq!(sentences[1].index(), 1);
+    assert!(sentences[1].delimiter().is_none());
+}
+
+#[test]
+fn test_sentences_2() {
+    let seg = SegmenterBuilder::new()
+        .in_delimiters(["。"])
+        .ex_delimiters(["\n", "</br>"])
+        .quotes([('「', '」')])
+        .build()
+        .unwrap();
+    let text = "私は「はい。」と答えた</br>良かった。\n";
+    let ranges: Vec<_> = seg.segment(text).collect();
+    let sentences: Vec<_> = seg.sentences(text).collect();
+    assert_eq!(
+        sentences.iter().map(|s| s.byte_range()).collect::<Vec<_>>(),
+        ranges.iter().map(|&(i, j)| i..j).collect::<Vec<_>>()
+    );
+    for s in &sentences {
+        let chars: String = text
+            .chars()
+            .skip(s.char_range().start)
+            .take(s.char_range().len())
+            .collect();
+        assert_eq!(chars, s.as_str());
+    }
+    let delimiters: Vec<_> = sentences
+        .iter()
+        .map(|s| s.delimiter().unwrap().as_str())
+        .collect();
+    assert_eq!(delimiters, vec!["</br>", "。"]);
+}
+
+#[test]
+fn test_sentences_merged_delimiter() {
+    let seg = SegmenterBuilder::new()
+        .in_delimiters(["。", "！", "？"])
+        .mergeable_delimiters(["！", "？"])
+        .build()
+        .unwrap();
+    let text = "本当？！はい。";
+    let sentences: Vec<_> = seg.sentences(text).collect();
+    assert_eq!(sentences[0].as_str(), "本当？！");
+    let delimiter = sentences[0].delimiter().unwrap();
+    assert_eq!(delimiter.as_str(), "？！");
+    assert_eq!(delimiter.byte_range(), 6..12);
+    assert!(delimiter.is_inclusive());
+}
+
+#[test]
+fn test_sentences_attached_delimiter() {
+    let seg = SegmenterBuilder::new()
+        .in_delimiters(["。"])
+        .trailing_attachers(["」", "）"])
+        .build()
+        .unwrap();
+    let text = "行った。」）と";
+    let sentences: Vec<_> = seg.sentences(text).collect();
+    assert_eq!(sentences[0].as_str(), "行った。」）");
+    let delimiter = sentences[0].delimiter().unwrap();
+    assert_eq!(delimiter.as_str(), "。」）");
+    assert_eq!(delimiter.byte_range(), 9..18);
+    assert!(delimiter.is_inclusive());
+    assert!(sentences[1].delimiter().is_none());
+}
+
+fn assert_stream_eq(seg: &Segmenter, text: &str, capacity: usize) {
+    let expected: Vec<_> = seg
+        .sentences(text)
+        .map(|s| {
+            (
+                s.as_str().to_string(),
+                s.byte_range(),
+                s.char_range(),
+                s.parent_quote(),
+                s.index(),
+            )
+        })
+        .collect();
+    let reader = std::io::BufReader::with_capacity(capacity, text.as_bytes());
+    let actual: Vec<_> = seg
+        .segment_reader(reader)
+        .map(|s| {
+            let s = s.unwrap();
+            (
+                s.as_str().to_string(),
+                s.byte_range(),
+                s.char_range(),
+                s.parent_quote(),
+                s.index(),
+            )
+        })
+        .collect();
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn test_stream_1() {
+    let seg = SegmenterBuilder::new()
+        .in_delimiters(["。", "。。。"])
+        .ex_delimiters(["\n", "\r\n", "\r"])
+        .quotes([('「', '」'), ('（', '）')])
+        .no_break_words(["モーニング娘。"])
+        .no_break_regex(Regex::new(r"\d(．)\d").unwrap())
+        .no_break_regex(Regex::new(r"(。{2,})。").unwrap())
+        .build()
+        .unwrap();
+    let text = "私は「はい。そうです。（嘘だけど。）」と答えた。\r\n\r\nモーニング娘。の\
+                新曲は３．１４分。。。。長い。。。\r（閉じない。括弧。\nなるほど\r\n";
+    for capacity in 1..8 {
+        assert_stream_eq(&seg, text, capacity);
+    }
+}
+
+#[test]
+fn test_stream_look_around() {
+    let seg = SegmenterBuilder::new()
+        .in_delimiters([".", "。"])
+        .ex_delimiters(["\n"])
+        .no_break_regex(Regex::new(r"(?m)^[0-9]+(\.)").unwrap())
+        .no_break_regex(Regex::new(r"\b(Mr\.)").unwrap())
+        .build()
+        .unwrap();
+    let text = "1. Hello Mr. Smith.Mr. Brown.\n2. Hi.2. Bye.\n10. OK";
+    for capacity in 1..8 {
+        assert_stream_eq(&seg, text, capacity);
+    }
+}
+
+#[test]
+fn test_stream_unclosed_quote() {
+    let seg = SegmenterBuilder::new()
+        .in_delimiters(["。"])
+        .quotes([('「', '」')])
+        .build()
+        .unwrap();
+    let text = "「はい。そうです。と答えた。」のか。いいえ。「違う。";
+    for capacity in 1..8 {
+        assert_stream_eq(&seg, text, capacity);
+    }
+}
+
+#[test]
+fn test_incremental_long_quote() {
+    let seg = SegmenterBuilder::new()
+        .in_delimiters(["。"])
+        .quotes([('「', '」')])
+        .build()
+        .unwrap();
+    // The quotation longer than 1 MiB is not regarded as unclosed.
+    let text = format!("「{}」と言った。はい。", "あいうえお。".repeat(60000));
+    let expected: Vec<_> = seg.sentences(&text).map(|s| s.byte_range()).collect();
+    assert_eq!(expected.len(), 2);
+    let mut inc = IncrementalSegmenter::new(&seg);
+    let mut sentences = vec![];
+    for chunk in text.as_bytes().chunks(1800) {
+        let chunk = std::str::from_utf8(chunk).unwrap();
+        sentences.extend(inc.push(chunk).into_iter().map(|s| s.byte_range()));
+    }
+    sentences.extend(inc.finish().into_iter().map(|s| s.byte_range()));
+    assert_eq!(sentences, expected);
+}
+
+#[test]
+fn test_stream_max_held_quote_len() {
+    let seg = SegmenterBuilder::new()
+        .in_delimiters(["。"])
+        .quotes([('「', '」')])
+        .build()
+        .unwrap();
+    let read = |text: &str| -> Vec<_> {
+        let reader = std::io::BufReader::with_capacity(64, text.as_bytes());
+        seg.segment_reader(reader)
+            .max_held_quote_len(1000)
+            .map(|s| s.unwrap().byte_range())
+            .collect()
+    };
+    // 300 bytes of the quotation are below the limit.
+    let text = format!("「{}」と。", "あいうえお。".repeat(50));
+    let expected: Vec<_> = seg.segment(&text).map(|(i, j)| i..j).collect();
+    assert_eq!(expected.len(), 1);
+    assert_eq!(read(&text), expected);
+    // 1800 bytes of the quotation exceed the limit, which is regarded as unclosed.
+    let text = format!("「{}」と。", "あいうえお。".repeat(100));
+    let expected: Vec<_> = seg.segment(&text).map(|(i, j)| i..j).collect();
+    assert_eq!(expected.len(), 1);
+    let expected: Vec<_> = seg
+        .segment(&text.replacen('「', "＊", 1))
+        .map(|(i, j)| i..j)
+        .collect();
+    assert_eq!(expected.len(), 101);
+    assert_eq!(read(&text), expected);
+}
+
+#[test]
+fn test_stream_template() {
+    let seg = crate::template::ja::general().build().unwrap();
+    let text = include_str!("../../data/gakumonno_susume.txt");
+    for capacity in [5, 4096] {
+        assert_stream_eq(&seg, text, capacity);
+    }
+}
+
+#[test]
+fn test_stream_invalid_utf8() {
+    let seg = SegmenterBuilder::new()
+        .in_delimiters(["。"])
+        .build()
+        .unwrap();
+    let bytes = b"abc\xff\xfe\xfd";
+    let result: Result<Vec<_>, _> = seg.segment_reader(&bytes[..]).collect();
+    assert_eq!(result.unwrap_err().kind(), std::io::ErrorKind::InvalidData);
+}
+
+fn assert_incremental_eq(seg: &Segmenter, text: &str, chunk_len: usize) {
+    let expected: Vec<_> = seg
+        .sentences(text)
+        .map(|s| {
+            (
+                s.as_str().to_string(),
+                s.byte_range(),
+                s.char_range(),
+                s.parent_quote(),
+            )
+        })
+        .collect();
+    let mut inc = IncrementalSegmenter::new(seg);
+    let mut sentences = vec![];
+    let chars: Vec<char> = text.chars().collect();
+    for chunk in chars.chunks(chunk_len) {
+        sentences.extend(inc.push(&chunk.iter().collect::<String>()));
+    }
+    sentences.extend(inc.finish());
+    let actual: Vec<_> = sentences
+        .into_iter()
+        .map(|s| {
+            (
+                s.as_str().to_string(),
+                s.byte_range(),
+                s.char_range(),
+                s.parent_quote(),
+            )
+        })
+        .collect();
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn test_incremental_nested_quotes() {
+    let seg = SegmenterBuilder::new()
+        .in_delimiters(["。"])
+        .quotes([('「', '」'), ('（', '）')])
+        .max_quote_level(1)
+        .unwrap()
+        .build()
+        .unwrap();
+    let text = "彼は「はい（そう。）。」と言った。「いいえ。」次。";
+    for chunk_len in 1..6 {
+        assert_incremental_eq(&seg, text, chunk_len);
+    }
+}
+
+#[test]
+fn test_incremental_multi_char_quotes() {
+    let seg = SegmenterBuilder::new()
+        .in_delimiters(["。"])
+        .quotes([("<", ">"), ("<<", ">>"), ("```", "```")])
+        .build()
+        .unwrap();
+    let text = "彼は<<はい。<そう。>>>と言った。```a。b。```<c。>。次。";
+    for chunk_len in 1..6 {
+        assert_incremental_eq(&seg, text, chunk_len);
+    }
+}
+
+#[test]
+fn test_incremental_quote_recovery() {
+    let text = "「はい（そう。」次。\n「長い。引用の。（途中。）」\n\n（いいえ。」）\n「終わり。";
+    for policy in [
+        QuoteMismatchPolicy::Skip,
+        QuoteMismatchPolicy::PopToMatch,
+        QuoteMismatchPolicy::Reset,
+    ] {
+        let seg = SegmenterBuilder::new()
+            .in_delimiters(["。"])
+            .ex_delimiters(["\n"])
+            .quotes([('「', '」'), ('（', '）')])
+            .max_quote_len(10)
+            .unwrap()
+            .quote_reset_delimiters(["\n\n"])
+            .quote_mismatch_policy(policy)
+            .build()
+            .unwrap();
+        for chunk_len in 1..6 {
+            assert_incremental_eq(&seg, text, chunk_len);
+        }
+        for capacity in 1..8 {
+            assert_stream_eq(&seg, text, capacity);
+        }
+    }
+}
+
+#[test]
+fn test_incremental_quote_policy() {
+    let seg = SegmenterBuilder::new()
+        .in_delimiters(["。"])
+        .ex_delimiters(["\n"])
+        .quotes([('「', '」'), ('『', '』')])
+        .quote_policy('「', QuotePolicy::SplitInside)
+        .quote_policy('『', QuotePolicy::SplitIfLongerThan(4))
+        .build()
+        .unwrap();
+    let text = "「あ。『い。う。』え。」お。『か』き。\n『く。け』「こ";
+    for chunk_len in 1..6 {
+        assert_incremental_eq(&seg, text, chunk_len);
+    }
+    for capacity in 1..8 {
+        assert_stream_eq(&seg, text, capacity);
+    }
+}
+
+#[test]
+fn test_incremental_word_normalization() {
+    let seg = SegmenterBuilder::new()
+        .in_delimiters(["。", "．"])
+        .no_break_words(["Mr.", "ガ。"])
+        .word_ascii_case_insensitive(true)
+        .word_normalization(WordNormalization::FoldWidth)
+        .build()
+        .unwrap();
+    let text = "ＭＲ．田中．ｶﾞ。ｶ。mr.山田。ｶﾞ".repeat(20);
+    for chunk_len in [1, 2, 3, 7] {
+        assert_incremental_eq(&seg, &text, chunk_len);
+    }
+    #[cfg(feature = "rayon")]
+    {
+        let text = text.repeat(1000);
+        let expected: Vec<_> = seg.segment(&text).collect();
+        assert_eq!(seg.segment_par(&text), expected);
+    }
+}
+
+#[test]
+fn test_incremental_word_boundary() {
+    let seg = SegmenterBuilder::new()
+        .in_delimiters([". ", "."])
+        .no_break_words_with_boundaries(
+            ["St. ", "etc."],
+            WordBoundary::NonAlphanumeric,
+            WordBoundary::NonAlphanumeric,
+        )
+        .build()
+        .unwrap();
+    let text = "1st. St. Mary. etc.etc. St. 2 etc.x St.".repeat(20);
+    for chunk_len in [1, 2, 3, 7] {
+        assert_incremental_eq(&seg, &text, chunk_len);
+    }
+    #[cfg(feature = "rayon")]
+    {
+        let text = text.repeat(1000);
+        let expected: Vec<_> = seg.segment(&text).collect();
+        assert_eq!(seg.segment_par(&text), expected);
+    }
+}
+
+#[test]
+fn test_incremental_word_parts() {
+    let seg = SegmenterBuilder::new()
+        .in_delimiters(["."])
+        .no_break_word_patterns(["e[.]g.", "U[.]S[.]A.", "[...]"])
+        .build()
+        .unwrap();
+    let text = "See e.g.The U.S.A.is big...right.e.g".repeat(20);
+    for chunk_len in [1, 2, 3, 7] {
+        assert_incremental_eq(&seg, &text, chunk_len);
+    }
+    #[cfg(feature = "rayon")]
+    {
+        let text = text.repeat(1000);
+        let expected: Vec<_> = seg.segment(&text).collect();
+        assert_eq!(seg.segment_par(&text), expected);
+    }
+}
+
+#[test]
+fn test_incremental_mergeable_delimiters() {
+    let seg = SegmenterBuilder::new()
+        .in_delimiters(["。", "！", "？", "!?"])
+        .ex_delimiters(["\n"])
+        .mergeable_delimiters(["。", "！", "？", "!?"])
+        .trailing_attachers(["」"])
+        .no_break_words(["娘。"])
+        .force_break_words(["？？"])
+        .build()
+        .unwrap();
+    let text = "えっ！？!?本当。。。」\nはぁ。娘。。疲れた？？？！\n".repeat(20);
+    for chunk_len in [1, 2, 3, 7] {
+        assert_incremental_eq(&seg, &text, chunk_len);
+    }
+    assert_stream_eq(&seg, &text, 1);
+    #[cfg(feature = "rayon")]
+    {
+        let text = text.repeat(1000);
+        let expected: Vec<_> = seg.segment(&text).collect();
+        assert_eq!(seg.segment_par(&text), expected);
+
+        let text = format!("はぁ{}", "。".repeat(100000));
+        assert_eq!(seg.segment_par(&text), vec![(0, text.len())]);
+    }
+}
+
+#[test]
+fn test_incremental_trailing_attachers() {
+    let seg = SegmenterBuilder::new()
+        .in_delimiters(["。", "！"])
+        .ex_delimiters(["\n"])
+        .trailing_attachers(["」", "）", "』", "※1"])
+        .quotes([('（', '）')])
+        .no_break_words(["娘。"])
+        .force_break_words(["！"])
+        .build()
+        .unwrap();
+    let text = "行った。」と言った。※1\n終わり。』）次は（笑。）娘。」です！」！\n".repeat(20);
+    for chunk_len in [1, 2, 3, 7] {
+        assert_incremental_eq(&seg, &text, chunk_len);
+    }
+    assert_stream_eq(&seg, &text, 1);
+    #[cfg(feature = "rayon")]
+    {
+        let text = text.repeat(1000);
+        let expected: Vec<_> = seg.segment(&text).collect();
+        assert_eq!(seg.segment_par(&text), expected);
+    }
+}
+
+#[test]
+fn test_incremental_bounded_delimiters() {
+    let seg = SegmenterBuilder::new()
+        .in_delimiters(["！"])
+        .in_delimiters_with_boundaries(
+            ["。", "。」"],
+            WordBoundary::Any,
+            WordBoundary::Class("[^」）]".to_string()),
+        )
+        .ex_delimiters_with_boundaries(
+            ["\n"],
+            WordBoundary::Class("[。！」]".to_string()),
+            WordBoundary::Any,
+        )
+        .quotes([('（', '）')])
+        .build()
+        .unwrap();
+    let text = "はい（笑。）いいえ。」と\n言った。\n「はい。」そう！\n".repeat(20);
+    for chunk_len in [1, 2, 3, 7] {
+        assert_incremental_eq(&seg, &text, chunk_len);
+    }
+    assert_stream_eq(&seg, &text, 1);
+    #[cfg(feature = "rayon")]
+    {
+        let text = text.repeat(1000);
+        let expected: Vec<_> = seg.segment(&text).collect();
+        assert_eq!(seg.segment_par(&text), expected);
+    }
+}
+
+#[test]
+fn test_incremental_delimiter_regex() {
+    let seg = SegmenterBuilder::new()
+        .in_delimiters(["。"])
+        .ex_delimiters(["\n"])
+        .in_delimiter_regex(Regex::new(r"(\. )[A-Z]").unwrap())
+        .ex_delimiter_regex(Regex::new(r"\n{2,}").unwrap())
+        .quotes([('「', '」')])
+        .no_break_words(["Mr. "])
+        .build()
+        .unwrap();
+    let text = "Mr. Smith came. He left\n\n\n「No. Yes。」と。\nOK. ".repeat(20);
+    for chunk_len in [1, 2, 3, 7] {
+        assert_incremental_eq(&seg, &text, chunk_len);
+    }
+    assert_stream_eq(&seg, &text, 1);
+
+    // Regex delimiters can be extended by subsequent text.
+    let seg = SegmenterBuilder::new()
+        .in_delimiters(["。"])
+        .in_delimiter_regex(Regex::new(r"(。a*b)").unwrap())
+        .build()
+        .unwrap();
+    let text = "z。aaab。aaz。b".repeat(20);
+    for chunk_len in [1, 2, 3, 7] {
+        assert_incremental_eq(&seg, &text, chunk_len);
+    }
+    assert_stream_eq(&seg, &text, 1);
+    #[cfg(feature = "rayon")]
+    {
+        let text = text.repeat(1000);
+        let expected: Vec<_> = seg.segment(&text).collect();
+        assert_eq!(seg.segment_par(&text), expected);
+    }
+}
+
+#[test]
+fn test_incremental_force_break() {
+    let seg = SegmenterBuilder::new()
+        .in_delimiters(["。"])
+        .ex_delimiters(["\n"])
+        .quotes([('「', '」')])
+        .no_break_words(["娘。\n"])
+        .force_break_words(["\n\n", "娘。"])
+        .force_break_regex(Regex::new(r"\n(第\d+条)").unwrap())
+        .build()
+        .unwrap();
+    let text = "「はい。\n\nいいえ。」娘。\n第1条。\n\n第23条「前\n第4条」。".repeat(20);
+    for chunk_len in [1, 2, 3, 7] {
+        assert_incremental_eq(&seg, &text, chunk_len);
+    }
+    assert_stream_eq(&seg, &text, 1);
+    #[cfg(feature = "rayon")]
+    {
+        let text = text.repeat(1000);
+        let expected: Vec<_> = seg.segment(&text).collect();
+        assert_eq!(seg.segment_par(&text), expected);
+    }
+}
+
+#[test]
+fn test_incremental_pending_text() {
+    let seg = SegmenterBuilder::new()
+        .in_delimiters(["。"])
+        .quotes([('「', '」')])
+        .build()
+        .unwrap();
+    let mut inc = IncrementalSegmenter::new(&seg);
+    assert!(inc.push("「はい。").is_empty());
+    assert!(inc.push("いいえ。").is_empty());
+    assert_eq!(inc.pending_text(), "「はい。いいえ。");
+    let sentences = inc.push("」そう。次は");
+    assert_eq!(sentences.len(), 1);
+    assert_eq!(sentences[0].as_str(), "「はい。いいえ。」そう。");
+    assert_eq!(inc.pending_text(), "次は");
+    let sentences = inc.finish();
+    assert_eq!(sentences[0].as_str(), "次は");
+    assert_eq!(sentences[0].index(), 1);
+}
+
+#[test]
+fn test_incremental_template() {
+    let seg = crate::template::ja::chat().build().unwrap();
+    let text = include_str!("../../data/gakumonno_susume.txt");
+    for chunk_len in [7, 100] {
+        assert_incremental_eq(&seg, text, chunk_len);
+    }
+}
+
+#[test]
+fn test_incremental_trim_policy() {
+    let seg = SegmenterBuilder::new()
+        .in_delimiters(["。"])
+        .ex_delimiters(["\n"])
+        .trim_policy(TrimPolicy::Unicode)
+        .drop_empty_sentences(true)
+        .build()
+        .unwrap();
+    let text = include_str!("../../data/wagahaiwa_nekodearu.txt");
+    for chunk_len in [1, 7, 100] {
+        assert_incremental_eq(&seg, text, chunk_len);
+    }
+    assert_stream_eq(&seg, text, 1);
+    assert!(seg
+        .segment(text)
+        .all(|(i, j)| i < j && !text[i..j].starts_with('　')));
+    #[cfg(feature = "rayon")]
+    {
+        let text = text.repeat(10);
+        let expected: Vec<_> = seg.segment(&text).collect();
+        assert_eq!(seg.segment_par(&text), expected);
+    }
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn test_segment_par_template() {
+    let texts = [
+        include_str!("../../data/gakumonno_susume.txt"),
+        include_str!("../../data/wagahaiwa_nekodearu.txt"),
+    ];
+    for builder in [
+        crate::template::ja::general(),
+        crate::template::ja::legal(),
+        crate::template::ja::chat(),
+    ] {
+        let seg = builder.build().unwrap();
+        for text in texts {
+            let expected: Vec<_> = seg.segment(text).collect();
+            assert_eq!(seg.segment_par(text), expected);
+        }
+    }
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn test_segment_par_long_quote() {
+    let seg = crate::template::ja::general().build().unwrap();
+    let body = include_str!("../../data/gakumonno_susume.txt");
+    for text in [
+        format!("「{body}」{body}"),
+        format!("{body}「{body}"),
+        format!("「{body}{body}{body}」"),
+    ] {
+        let expected: Vec<_> = seg.segment(&text).collect();
+        assert_eq!(seg.segment_par(&text), expected);
+    }
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn test_segment_par_quote_recovery() {
+    let text =
+        "「はい（そう。」次。\n「長い。引用の。（途中。）」\n\n（いいえ。」）\n".repeat(5000);
+    for policy in [
+        QuoteMismatchPolicy::Skip,
+        QuoteMismatchPolicy::PopToMatch,
+        QuoteMismatchPolicy::Reset,
+    ] {
+        let seg = SegmenterBuilder::new()
+            .in_delimiters(["。"])
+            .ex_delimiters(["\n"])
+            .quotes([('「', '」'), ('（', '）')])
+            .max_quote_len(10)
+            .unwrap()
+            .quote_reset_delimiters(["\n\n"])
+            .quote_mismatch_policy(policy)
+            .build()
+            .unwrap();
+        let expected: Vec<_> = seg.segment(&text).collect();
+        assert_eq!(seg.segment_par(&text), expected);
+    }
+}
+
+#[test]
+fn test_stream_quote_policy_novel() {
+    let seg = crate::template::ja::general()
+        .quote_policy('「', QuotePolicy::SplitIfLongerThan(40))
+        .build()
+        .unwrap();
+    let text = include_str!("../../data/wagahaiwa_nekodearu.txt");
+    assert!(seg.sentences(text).any(|s| s.parent_quote().is_some()));
+    assert_stream_eq(&seg, text, 4096);
+    #[cfg(feature = "rayon")]
+    {
+        let expected: Vec<_> = seg.segment(text).collect();
+        assert_eq!(seg.segment_par(text), expected);
+    }
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn test_segment_par_regex() {
+    let seg = SegmenterBuilder::new()
+        .in_delimiters(["。"])
+        .ex_delimiters(["\n", "\r\n"])
+        .no_break_regex(Regex::new(r"(\n)\n").unwrap())
+        .no_break_words(["。\n"])
+        .build()
+        .unwrap();
+    let text = "あいう。\nえお\r\n\nか。\r\n".repeat(20000);
+    let expected: Vec<_> = seg.segment(&text).collect();
+    assert_eq!(seg.segment_par(&text), expected);
+}
+
+#[test]
+fn test_stream_protected_delimiter() {
+    let seg = SegmenterBuilder::new()
+        .ex_delimiters(["\n"])
+        .no_break_words(["\n"])
+        .build()
+        .unwrap();
+    let text = "a\n\nb\n\n\nc";
+    for capacity in 1..4 {
+        assert_stream_eq(&seg, text, capacity);
+    }
+}
+
+#[test]
+fn test_segment_batch() {
+    let seg = crate::template::ja::general().build().unwrap();
+    let text = include_str!("../../data/gakumonno_susume.txt");
+    let docs: Vec<_> = text.split("\n\n").collect();
+    let batch = seg.segment_batch(&docs);
+    assert_eq!(batch.len(), docs.len());
+    for (doc, ranges) in docs.iter().zip(batch.iter()) {
+        let expected: Vec<_> = seg.segment(doc).collect();
+        assert_eq!(ranges, expected);
+    }
+    assert_eq!(batch.ranges().len(), *batch.offsets().last().unwrap());
+}
+
+#[test]
+fn test_segment_with_reused_scratch() {
+    let seg = SegmenterBuilder::new()
+        .in_delimiters(["。"])
+        .quotes([('「', '」')])
+        .build()
+        .unwrap();
+    let mut scratch = SegmentScratch::new();
+    for text in [
+        "「はい。そうです。いいえ。」",
+        "はい。そうです。",
+        "「あ。",
+        "い。う。",
+    ] {
+        let expected: Vec<_> = seg.segment(text).collect();
+        let ranges: Vec<_> = seg.segment_with(text, &mut scratch).collect();
+        assert_eq!(ranges, expected);
+    }
+}
+
+#[test]
+fn test_stream_symmetric_quote() {
+    let text = "I don't know. \"Go. Now.\" he said.\n'Yes. No.' OK. \"Open. End.";
+    for rule in [SymmetricQuoteRule::Toggle, SymmetricQuoteRule::WordBoundary] {
+        let seg = SegmenterBuilder::new()
+            .in_delimiters([". "])
+            .ex_delimiters(["\n"])
+            .quotes([('"', '"'), ('\'', '\'')])
+            .symmetric_quote_rule(rule)
+            .build()
+            .unwrap();
+        for capacity in 1..8 {
+            assert_stream_eq(&seg, text, capacity);
+        }
+    }
+}
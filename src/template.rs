@@ -1,2 +1,28 @@
 //! Basic segmentation rules.
+//!
+//! The rules are provided as [`SegmenterBuilder`]s,
+//! which can be further customized before building.
 pub mod ja;
+
+use crate::SegmenterBuilder;
+
+/// Names of the templates available in [`by_name`].
+pub const NAMES: [&str; 3] = ["ja", "ja-legal", "ja-chat"];
+
+/// Returns the template of the given name.
+///
+/// | Name       | Template         |
+/// |------------|------------------|
+/// | `ja`       | [`ja::general`]  |
+/// | `ja-legal` | [`ja::legal`]    |
+/// | `ja-chat`  | [`ja::chat`]     |
+///
+/// `None` is returned if the name is unknown.
+pub fn by_name(name: &str) -> Option<SegmenterBuilder> {
+    match name {
+        "ja" => Some(ja::general()),
+        "ja-legal" => Some(ja::legal()),
+        "ja-chat" => Some(ja::chat()),
+        _ => None,
+    }
+}
@@ -0,0 +1,193 @@
+//! Segmentation rules for Japanese.
+//!
+//! Each function returns a [`SegmenterBuilder`] with pre-defined rules,
+//! so you can add your own rules before building a segmenter.
+//!
+//! ```rust
+//! use easy_segmenter::template::ja;
+//!
+//! let seg = ja::general()
+//!     .no_break_words(["モーニング娘。"])
+//!     .build()
+//!     .unwrap();
+//! let text = "モーニング娘。の新曲は３．１４分です。「はい。そうです。」";
+//! let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+//! let expected = vec!["モーニング娘。の新曲は３．１４分です。", "「はい。そうです。」"];
+//! assert_eq!(sentences, expected);
+//! ```
+use regex::Regex;
+
+use crate::SegmenterBuilder;
+
+/// Delimiters for line breaks.
+const LINE_BREAKS: [&str; 3] = ["\n", "\r\n", "\r"];
+
+/// Pairs of full-width brackets commonly used in Japanese text.
+const FULL_WIDTH_QUOTES: [(char, char); 5] = [
+    ('「', '」'),
+    ('『', '』'),
+    ('（', '）'),
+    ('【', '】'),
+    ('《', '》'),
+];
+
+/// Returns rules for general Japanese prose such as novels and news articles.
+///
+/// - Inclusive delimiters: `。`, `．`, `？`, `！`, `?`, `!`
+/// - Exclusive delimiters: line breaks
+/// - Quotations: `「」`, `『』`, `（）`, `【】`, `《》`, `()`
+/// - Mergeable delimiters: all the inclusive delimiters, so runs such as `。。。` and `？！`
+///   end one sentence
+/// - No-break regex: decimal points such as `３．１４`
+///
+/// # Examples
+///
+/// ```
+/// use easy_segmenter::template::ja;
+///
+/// let seg = ja::general().build().unwrap();
+/// let text = "はぁ。。。疲れた！？\n円周率は３．１４です。";
+/// let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+/// let expected = vec!["はぁ。。。", "疲れた！？", "円周率は３．１４です。"];
+/// assert_eq!(sentences, expected);
+/// ```
+pub fn general() -> SegmenterBuilder {
+    let delimiters = ["。", "．", "？", "！", "?", "!"];
+    SegmenterBuilder::new()
+        .in_delimiters(delimiters)
+        .ex_delimiters(LINE_BREAKS)
+        .mergeable_delimiters(delimiters)
+        .quotes(FULL_WIDTH_QUOTES)
+        .quotes([('(', ')')])
+        .no_break_regex(decimal_point())
+}
+
+/// Returns rules for Japanese legal documents such as contracts and statutes.
+///
+/// - Inclusive delimiters: `。`
+/// - Exclusive delimiters: line breaks
+/// - Quotations: `「」`, `『』`, `（）`, `【】`, `《》`, `()`
+/// - Mergeable delimiters: `。`, so runs such as `。。` end one sentence
+/// - No-break regex: decimal points such as `３．１４`
+///
+/// As in [`general`], ellipses such as `…` and `・・・` are not delimiters
+/// and never break a sentence.
+///
+/// The maximum nested level of quotations is set to 5 since legal documents
+/// often have deeply nested parentheses.
+///
+/// # Examples
+///
+/// ```
+/// use easy_segmenter::template::ja;
+///
+/// let seg = ja::legal().build().unwrap();
+/// let text = "第１条　甲は、乙に対し、本件業務（以下「本業務」という。）を委託する。\n第２条　…";
+/// let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+/// let expected = vec![
+///     "第１条　甲は、乙に対し、本件業務（以下「本業務」という。）を委託する。",
+///     "第２条　…",
+/// ];
+/// assert_eq!(sentences, expected);
+/// ```
+pub fn legal() -> SegmenterBuilder {
+    SegmenterBuilder::new()
+        .in_delimiters(["。"])
+        .ex_delimiters(LINE_BREAKS)
+        .mergeable_delimiters(["。"])
+        .quotes(FULL_WIDTH_QUOTES)
+        .quotes([('(', ')')])
+        .no_break_regex(decimal_point())
+        .max_quote_level(5)
+        .unwrap()
+}
+
+/// Returns rules for casual Japanese text such as chats and social media posts.
+///
+/// - Inclusive delimiters: `。`, `．`, `？`, `！`, `?`, `!`, `♪`, `…`
+/// - Exclusive delimiters: line breaks
+/// - Quotations: `「」`, `『』`, `（）`, `【】`, `《》`
+/// - Mergeable delimiters: all the inclusive delimiters, so runs such as `！！` and `…。`
+///   end one sentence
+/// - No-break regex: decimal points such as `３．１４`
+///
+/// ASCII parentheses are not regarded as quotations
+/// because they are often used in unpaired emoticons such as `(^^;` and `:)`.
+///
+/// # Examples
+///
+/// ```
+/// use easy_segmenter::template::ja;
+///
+/// let seg = ja::chat().build().unwrap();
+/// let text = "まじで！？やったー♪♪明日ね…";
+/// let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+/// let expected = vec!["まじで！？", "やったー♪♪", "明日ね…"];
+/// assert_eq!(sentences, expected);
+/// ```
+pub fn chat() -> SegmenterBuilder {
+    let delimiters = ["。", "．", "？", "！", "?", "!", "♪", "…"];
+    SegmenterBuilder::new()
+        .in_delimiters(delimiters)
+        .ex_delimiters(LINE_BREAKS)
+        .mergeable_delimiters(delimiters)
+        .quotes(FULL_WIDTH_QUOTES)
+        .no_break_regex(decimal_point())
+}
+
+fn decimal_point() -> Regex {
+    Regex::new(r"[0-9０-９]([．.])[0-9０-９]").unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_general_quotes() {
+        let seg = general().build().unwrap();
+        let text = "私は『はい。（嘘だけど。）』と答えた。";
+        let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+        let expected = vec!["私は『はい。（嘘だけど。）』と答えた。"];
+        assert_eq!(sentences, expected);
+    }
+
+    #[test]
+    fn test_general_sequences() {
+        let seg = general().build().unwrap();
+        let text = "えっ？！本当．．．？\r\nはい。。";
+        let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+        let expected = vec!["えっ？！", "本当．．．？", "はい。。"];
+        assert_eq!(sentences, expected);
+    }
+
+    #[test]
+    fn test_legal_decimal_point() {
+        let seg = legal().build().unwrap();
+        let text = "利率は年３．５％とする。ただし、甲乙協議の上変更できる。";
+        let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+        let expected = vec![
+            "利率は年３．５％とする。",
+            "ただし、甲乙協議の上変更できる。",
+        ];
+        assert_eq!(sentences, expected);
+    }
+
+    #[test]
+    fn test_legal_sequences() {
+        let seg = legal().build().unwrap();
+        let text = "以下略。。。\n第２条　甲は…乙に対し・・・通知する。";
+        let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+        let expected = vec!["以下略。。。", "第２条　甲は…乙に対し・・・通知する。"];
+        assert_eq!(sentences, expected);
+    }
+
+    #[test]
+    fn test_chat_emoticon() {
+        let seg = chat().build().unwrap();
+        let text = "楽しかった(^^;また行こうね。うん:)";
+        let sentences: Vec<_> = seg.segment(text).map(|(i, j)| &text[i..j]).collect();
+        let expected = vec!["楽しかった(^^;また行こうね。", "うん:)"];
+        assert_eq!(sentences, expected);
+    }
+}
@@ -0,0 +1,71 @@
+#![cfg(feature = "cli")]
+
+use std::fs;
+use std::process::{Command, Output};
+
+fn run(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_easy-segmenter"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn test_files_in_order() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut paths = vec![];
+    let mut expected = String::new();
+    for i in 0..10 {
+        // Earlier files are longer so that later ones tend to finish first.
+        let text = format!("ファイル{i}です。").repeat((10 - i) * 1000);
+        let path = dir.path().join(format!("{i}.txt"));
+        fs::write(&path, &text).unwrap();
+        paths.push(path.to_str().unwrap().to_string());
+        expected.push_str(&format!("ファイル{i}です。\n").repeat((10 - i) * 1000));
+    }
+    let mut args = vec!["--jobs", "4"];
+    args.extend(paths.iter().map(String::as_str));
+    let output = run(&args);
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), expected);
+
+    let mut args = vec!["--format", "tsv"];
+    args.extend(paths.iter().map(String::as_str));
+    let output = run(&args);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let files: Vec<_> = stdout
+        .lines()
+        .map(|line| line.split('\t').next().unwrap())
+        .collect();
+    let mut expected = vec![];
+    for (i, path) in paths.iter().enumerate() {
+        expected.extend(std::iter::repeat_n(path.as_str(), (10 - i) * 1000));
+    }
+    assert_eq!(files, expected);
+}
+
+#[test]
+fn test_missing_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("a.txt");
+    fs::write(&path, "はい。そうです。").unwrap();
+    let missing = dir.path().join("missing.txt");
+    let output = run(&[
+        path.to_str().unwrap(),
+        missing.to_str().unwrap(),
+        path.to_str().unwrap(),
+    ]);
+    assert!(!output.status.success());
+    // The results before the missing file are written.
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "はい。\nそうです。\n"
+    );
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.starts_with("easy-segmenter: "), "{stderr}");
+    assert!(stderr.contains(missing.to_str().unwrap()), "{stderr}");
+
+    let output = run(&[missing.to_str().unwrap()]);
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+}